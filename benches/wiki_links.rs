@@ -0,0 +1,56 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use scraper::Selector;
+use scraprs::wiki::{parse_wiki_links, LinkFilter};
+
+static ARTICLE_HTML: &str = include_str!("../tests/fixtures/wiki_article.html");
+
+// Paragraphs of the article are repeated, as long articles hold thousands of links
+static PARAGRAPH_REPEATS: usize = 500;
+
+/// Large article, with the paragraphs of the saved article fixture repeated many times over.
+fn large_article() -> String {
+    let start = ARTICLE_HTML
+        .find("<p>")
+        .expect("fixture to have paragraphs");
+    let end = ARTICLE_HTML.find("<h2>").expect("fixture to have sections");
+
+    let (head, rest) = ARTICLE_HTML.split_at(start);
+    let (paragraphs, tail) = rest.split_at(end - start);
+    format!("{head}{}{tail}", paragraphs.repeat(PARAGRAPH_REPEATS))
+}
+
+fn parse_links(c: &mut Criterion) {
+    let html = large_article();
+    let filter = LinkFilter {
+        dedup: true,
+        ..LinkFilter::default()
+    };
+
+    let mut group = c.benchmark_group("parse_wiki_links");
+    group.throughput(Throughput::Bytes(html.len() as u64));
+    group.bench_function("large_article", |b| {
+        b.iter(|| parse_wiki_links(black_box(&html), filter.clone()).expect("article to parse"))
+    });
+    group.throughput(Throughput::Bytes(ARTICLE_HTML.len() as u64));
+    group.bench_function("small_article", |b| {
+        b.iter(|| {
+            parse_wiki_links(black_box(ARTICLE_HTML), filter.clone()).expect("article to parse")
+        })
+    });
+    group.finish();
+}
+
+// Cost of parsing the selectors of the link parser, which are compiled once into statics instead
+fn parse_selectors(c: &mut Criterion) {
+    c.bench_function("selector_parse", |b| {
+        b.iter(|| {
+            (
+                Selector::parse(black_box("div#bodyContent")).expect("to be a valid selector"),
+                Selector::parse(black_box("p a[href]")).expect("to be a valid selector"),
+            )
+        })
+    });
+}
+
+criterion_group!(benches, parse_links, parse_selectors);
+criterion_main!(benches);
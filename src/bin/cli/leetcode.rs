@@ -0,0 +1,890 @@
+use crate::shared::SharedArgs;
+use chrono::{
+    format::{Item, StrftimeItems},
+    Days, FixedOffset, Local, NaiveDate, Utc,
+};
+use clap::{error::ErrorKind, ValueEnum};
+use once_cell::sync::{Lazy, OnceCell};
+use scraprs::leetcode::{
+    add_session_cookies, parse_history, problem_file_name, problems_to_csv, prune_history,
+    scrape_all, scrape_contest, scrape_potd, upsert_problems, ContestDocument, DatedPotd,
+    HistoryDocument, OutputFormat, PotdDocument, PotdInfo, ProblemsDocument, RowSelectors,
+    ScrapeError, ScrapeOptions, SessionCookies, LEETCODE_DOMAIN,
+};
+use std::{
+    error::Error,
+    fmt,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use thirtyfour::{
+    common::capabilities::firefox::FirefoxPreferences,
+    prelude::{WebDriverError, WebDriverResult},
+    Capabilities, CapabilitiesHelper, ChromeCapabilities, FirefoxCapabilities, Proxy, WebDriver,
+};
+use tracing::{info, warn};
+use tracing_subscriber::EnvFilter;
+
+pub static EXIT_CODES: &str = "\
+Exit codes:
+  0  Scraping succeeded
+  1  Any other failure
+  2  WebDriver session could not be set up ( also with `--check` )
+  3  Page did not hydrate in time
+  4  Page could not be parsed, likely due to a layout change
+  5  Output could not be written
+  130  Scraping was interrupted with Ctrl-C";
+
+/// Scrapes the LeetCode problem of the day ( POTD ), the whole problemset or a contest, through a WebDriver session.
+#[derive(clap::Args)]
+pub struct Args {
+    // Output file path, set from the shared `--output`
+    #[arg(skip)]
+    output: Option<String>,
+
+    /// WebDriver endpoint the browser session is requested from
+    #[arg(env = "WEBDRIVER_URL", default_value = "http://localhost:9515")]
+    webdriver_url: String,
+
+    /// Browser the WebDriver session is requested for ( geckodriver usually listens on port 4444 )
+    #[arg(long, value_enum, env = "BROWSER", default_value_t = Browser::Chrome)]
+    browser: Browser,
+
+    /// LeetCode site the problems are scraped from ( eg. https://leetcode.cn ), whose layout might need adjusted selectors
+    #[arg(long, env = "LEETCODE_DOMAIN", default_value = LEETCODE_DOMAIN, value_parser = parse_domain)]
+    domain: String,
+
+    /// TOML file overriding the problem row selectors ( problem_anchor, solution_anchor and difficulty_label ), for when the LeetCode layout changes
+    #[arg(long, env = "LEETCODE_SELECTORS", value_parser = parse_selectors)]
+    selectors: Option<RowSelectors>,
+
+    /// File of the cookies of a signed in session ( LEETCODE_SESSION=...; csrftoken=... ) to scrape the premium problems with, instead of the LEETCODE_COOKIES env variable
+    #[arg(long, env = "LEETCODE_COOKIES_FILE")]
+    cookies_file: Option<PathBuf>,
+
+    // Serialization format of the output, set from the shared `--format`, where csv only holds the problem listing fields
+    #[arg(skip = OutputFormat::Toml)]
+    format: OutputFormat,
+
+    /// Serialize the output compactly instead of pretty-printed, for TOML and JSON
+    #[arg(long)]
+    compact: bool,
+
+    /// Show the browser window and pause on every step, which is useful for debugging selectors
+    #[arg(long)]
+    headful: bool,
+
+    /// Scraping attempts, retried with an exponential backoff as the problemset page is flaky to hydrate
+    #[arg(long, env = "SCRAPE_ATTEMPTS", default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+    attempts: u32,
+
+    /// Delay in milliseconds before the first retry, doubled on every attempt after it up to 5 minutes
+    #[arg(long, env = "SCRAPE_RETRY_DELAY_MS", default_value_t = 1000, value_parser = clap::value_parser!(u64))]
+    retry_delay_ms: u64,
+
+    /// Scrape the topic tags, which requires an extra visit to the problem page
+    #[arg(long)]
+    with_tags: bool,
+
+    /// Scrape the problem statement, which requires an extra visit to the problem page
+    #[arg(long)]
+    with_description: bool,
+
+    /// Scrape the constraints listed in the problem statement, which requires an extra visit to the problem page
+    #[arg(long)]
+    with_constraints: bool,
+
+    /// Scrape the example test cases of the problem statement, which requires an extra visit to the problem page
+    #[arg(long)]
+    with_examples: bool,
+
+    /// Scrape the company tags, which requires an extra visit to the problem page and session cookies of a premium account
+    #[arg(long)]
+    with_companies: bool,
+
+    /// Scrape the like and dislike counts, which requires an extra visit to the problem page
+    #[arg(long)]
+    with_votes: bool,
+
+    /// Scrape the community solutions count, which requires an extra visit to the problem page
+    #[arg(long)]
+    with_solution_count: bool,
+
+    /// Scrape the similar questions, which requires an extra visit to the problem page
+    #[arg(long)]
+    with_similar: bool,
+
+    /// Scrape the starter code templates, which requires an extra visit to the problem page
+    #[arg(long)]
+    with_templates: bool,
+
+    /// Only keep the starter code template of this language ( eg. python3, rust or C++ )
+    #[arg(long, requires = "with_templates")]
+    lang: Option<String>,
+
+    /// Convert the problem statement from html to Markdown
+    #[arg(long, requires = "with_description")]
+    plain_text: bool,
+
+    /// Append the POTD to a history of entries in the output file
+    #[arg(long, conflicts_with = "all")]
+    append: bool,
+
+    /// Prune the history entries older than this, as a date ( eg. 2024-01-31 ) or a number of days or weeks ago ( eg. 30d or 4w )
+    #[arg(long, requires = "append", value_parser = parse_since)]
+    since: Option<Since>,
+
+    /// Scrape the whole problemset instead of only the POTD
+    #[arg(long)]
+    all: bool,
+
+    /// Scrape the problems of a weekly or biweekly contest instead of the POTD, by its slug ( eg. weekly-contest-400 )
+    #[arg(long, conflicts_with_all = ["all", "append"], value_parser = parse_contest_slug)]
+    contest: Option<String>,
+
+    /// Timeout in seconds of every wait for the page to hydrate, which can be bumped on slow networks
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    timeout_secs: Option<u64>,
+
+    /// Interval in milliseconds at which every wait for the page to hydrate is polled, tighter for less latency or looser for less CPU
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    poll_interval_ms: Option<u64>,
+
+    /// Save a PNG screenshot of the page to this path when scraping fails
+    #[arg(long, env = "SCRAPE_FAILURE_SCREENSHOT")]
+    failure_screenshot: Option<PathBuf>,
+
+    /// Dump the html source of the page to this path when scraping fails
+    #[arg(long, env = "SCRAPE_FAILURE_SOURCE")]
+    failure_source: Option<PathBuf>,
+
+    /// Write every problem scraped with `--all` to its own `{number}-{slug}.{ext}` file in this directory
+    #[arg(long, requires = "all")]
+    output_dir: Option<PathBuf>,
+
+    /// Overwrite the problem files which already exist in the output directory, instead of skipping them
+    #[arg(long, requires = "output_dir")]
+    overwrite: bool,
+
+    /// Upsert the scraped problems into the `problems` table of this SQLite database, keyed by their number, instead of writing the output
+    #[arg(long, conflicts_with_all = ["output_dir", "append", "dry_run"])]
+    sqlite: Option<PathBuf>,
+
+    /// Only check that the WebDriver is reachable, by opening about:blank in a session, without scraping
+    #[arg(long, conflicts_with_all = ["all", "append", "dry_run"])]
+    check: bool,
+
+    /// Scrape as usual, but print the output to stderr instead of writing any file
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Upper bound on the problemset pages scraped with `--all`
+    #[arg(long, requires = "all")]
+    max_pages: Option<u32>,
+
+    /// Format of the scrape date, with strftime specifiers ( eg. %Y-%m-%d )
+    #[arg(long, default_value = "%Y%m%d", value_parser = parse_date_format)]
+    date_format: String,
+
+    /// Timezone of the scrape date, as utc, local or a fixed offset ( eg. +05:30 ), as the POTD rolls over at UTC midnight
+    #[arg(long, default_value = "utc", value_parser = parse_timezone)]
+    timezone: Timezone,
+}
+
+#[derive(Clone, ValueEnum)]
+enum Browser {
+    Chrome,
+    Firefox,
+}
+
+/// Output format parser, listing the supported formats on failure.
+fn parse_format(format: &str) -> Result<OutputFormat, String> {
+    format
+        .parse()
+        .map_err(|err| format!("{err}, expected one of toml, json, yaml or csv"))
+}
+
+/**
+Date format parser, which only accepts formats whose dates can be parsed back.
+Dates have to round-trip, as the POTD history is deduplicated by its dates.
+*/
+fn parse_date_format(format: &str) -> Result<String, String> {
+    if StrftimeItems::new(format).any(|item| item == Item::Error) {
+        return Err(format!("invalid date format '{format}'"));
+    }
+
+    let now = Utc::now();
+    match NaiveDate::parse_from_str(&now.format(format).to_string(), format) {
+        Ok(date) if date == now.date_naive() => Ok(format.to_string()),
+        _ => Err(format!(
+            "date format '{format}' should hold the year, month and day ( eg. %Y%m%d )"
+        )),
+    }
+}
+
+/// Timezone the scrape date is taken in.
+#[derive(Clone)]
+enum Timezone {
+    Utc,
+    Local,
+    Offset(FixedOffset),
+}
+
+impl Timezone {
+    /// Current date in this timezone.
+    fn date(&self) -> NaiveDate {
+        match self {
+            Self::Utc => Utc::now().date_naive(),
+            Self::Local => Local::now().date_naive(),
+            Self::Offset(offset) => Utc::now().with_timezone(offset).date_naive(),
+        }
+    }
+
+    /// Current date in this timezone, in the given format.
+    fn today(&self, format: &str) -> String {
+        match self {
+            Self::Utc => Utc::now().format(format).to_string(),
+            Self::Local => Local::now().format(format).to_string(),
+            Self::Offset(offset) => Utc::now().with_timezone(offset).format(format).to_string(),
+        }
+    }
+}
+
+/// Oldest history entries kept, either from a date or from a number of days before the current date.
+#[derive(Clone)]
+enum Since {
+    Date(NaiveDate),
+    DaysAgo(u64),
+}
+
+impl Since {
+    /// Date of the oldest entries kept, with the relative ones taken from the current date in the timezone.
+    fn date(&self, timezone: &Timezone) -> NaiveDate {
+        match self {
+            Self::Date(date) => *date,
+            Self::DaysAgo(days) => timezone
+                .date()
+                .checked_sub_days(Days::new(*days))
+                .unwrap_or(NaiveDate::MIN),
+        }
+    }
+}
+
+/// Since parser, of either an ISO date ( eg. 2024-01-31 ) or a relative number of days or weeks ( eg. 30d or 4w ).
+fn parse_since(since: &str) -> Result<Since, String> {
+    let relative = |count: &str, days: u64| {
+        count
+            .parse::<u64>()
+            .ok()
+            .and_then(|count| count.checked_mul(days))
+            .map(Since::DaysAgo)
+    };
+    let parsed = match since.strip_suffix('d') {
+        Some(count) => relative(count, 1),
+        None => match since.strip_suffix('w') {
+            Some(count) => relative(count, 7),
+            None => NaiveDate::parse_from_str(since, "%Y-%m-%d")
+                .ok()
+                .map(Since::Date),
+        },
+    };
+
+    parsed.ok_or_else(|| {
+        format!("invalid since '{since}', expected a date like 2024-01-31 or a relative one like 30d or 4w")
+    })
+}
+
+/// Contest slug parser, of the lowercase words joined by dashes that contest urls end with.
+fn parse_contest_slug(slug: &str) -> Result<String, String> {
+    match !slug.is_empty()
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        true => Ok(slug.to_string()),
+        false => Err(format!(
+            "invalid contest slug '{slug}', expected one like weekly-contest-400"
+        )),
+    }
+}
+
+/// Selector overrides parser, reading and validating the config file before any WebDriver session is requested.
+fn parse_selectors(path: &str) -> Result<RowSelectors, String> {
+    let config = std::fs::read_to_string(path)
+        .map_err(|err| format!("could not read the selectors file '{path}' ( {err} )"))?;
+    RowSelectors::from_toml(&config).map_err(|err| err.to_string())
+}
+
+/// Domain parser, of an http(s) origin without any path, as the problem links are resolved on it.
+fn parse_domain(domain: &str) -> Result<String, String> {
+    let url =
+        reqwest::Url::parse(domain).map_err(|err| format!("invalid domain '{domain}': {err}"))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() || url.path() != "/" {
+        return Err(format!(
+            "invalid domain '{domain}', expected an origin like https://leetcode.cn"
+        ));
+    }
+
+    Ok(url.origin().ascii_serialization())
+}
+
+/// Timezone parser, of either utc, local or a fixed offset from UTC ( eg. +05:30 or -08:00 ).
+fn parse_timezone(timezone: &str) -> Result<Timezone, String> {
+    let invalid =
+        || format!("invalid timezone '{timezone}', expected utc, local or an offset like +05:30");
+
+    match timezone.to_lowercase().as_str() {
+        "utc" => return Ok(Timezone::Utc),
+        "local" => return Ok(Timezone::Local),
+        _ => {}
+    }
+
+    let (sign, offset) = match timezone.split_at_checked(1).ok_or_else(invalid)? {
+        ("+", offset) => (1, offset),
+        ("-", offset) => (-1, offset),
+        _ => return Err(invalid()),
+    };
+    let (hours, minutes) = offset.split_once(':').unwrap_or((offset, "0"));
+    let hours = hours.parse::<i32>().map_err(|_| invalid())?;
+    let minutes = minutes.parse::<i32>().map_err(|_| invalid())?;
+    // Offsets are checked before they are computed, so that a large one can't overflow
+    if !(0..=14).contains(&hours) || !(0..60).contains(&minutes) {
+        return Err(invalid());
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+        .map(Timezone::Offset)
+        .ok_or_else(invalid)
+}
+
+// Arguments are set once by the entry point, before anything reads them
+static PARSED_ARGS: OnceCell<Args> = OnceCell::new();
+static ARGS: Lazy<&Args> = Lazy::new(|| {
+    PARSED_ARGS
+        .get()
+        .expect("arguments to be set by the entry point")
+});
+
+// Session cookies are read once by the entry point, and are never logged
+static SESSION_COOKIES: OnceCell<SessionCookies> = OnceCell::new();
+
+// Browser window is also shown with `HEADLESS=0`
+static HEADFUL: Lazy<bool> =
+    Lazy::new(|| ARGS.headful || std::env::var("HEADLESS").is_ok_and(|headless| headless == "0"));
+
+// Retry delays double on every attempt, though never past this cap however many attempts are made
+static MAX_RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+
+// Set on Ctrl-C, so that scraping the whole problemset stops and the problems scraped so far are written
+static INTERRUPTED: Lazy<Arc<AtomicBool>> = Lazy::new(Arc::default);
+
+/// Errors of a whole run, categorized by the exit code they are reported with.
+#[derive(Debug)]
+enum RunError {
+    Connect(WebDriverError),
+    Scrape(ScrapeError),
+    Output(Box<dyn Error>),
+    Interrupted,
+}
+
+impl RunError {
+    fn exit_code(&self) -> u8 {
+        match self {
+            Self::Connect(_) => 2,
+            Self::Scrape(ScrapeError::WebDriver(WebDriverError::Timeout(_))) => 3,
+            // Missing elements are a layout change, like the other parse failures
+            Self::Scrape(ScrapeError::WebDriver(WebDriverError::NoSuchElement(_))) => 4,
+            Self::Scrape(ScrapeError::WebDriver(_)) => 1,
+            Self::Scrape(_) => 4,
+            Self::Output(_) => 5,
+            Self::Interrupted => 130,
+        }
+    }
+}
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connect(err) => write!(f, "webdriver session failure: {err}"),
+            Self::Scrape(err) => write!(f, "{err}"),
+            Self::Output(err) => write!(f, "output failure: {err}"),
+            Self::Interrupted => write!(f, "scraping was interrupted"),
+        }
+    }
+}
+
+impl From<ScrapeError> for RunError {
+    fn from(err: ScrapeError) -> Self {
+        Self::Scrape(err)
+    }
+}
+
+/// Serializes the document in the requested output format, or just its problems as CSV rows.
+fn serialize<'a, T: serde::Serialize>(
+    document: &T,
+    problems: impl IntoIterator<Item = &'a PotdInfo>,
+) -> Result<String, RunError> {
+    match ARGS.format {
+        OutputFormat::Csv => problems_to_csv(problems),
+        format => format.serialize(document, ARGS.compact),
+    }
+    .map_err(RunError::Output)
+}
+
+/// Writes out the serialized output.
+fn write_output(output: String) -> Result<(), RunError> {
+    if ARGS.dry_run {
+        info!(path = ARGS.output, "dry run, skipping the output write");
+        eprint!("{output}");
+        return Ok(());
+    }
+
+    match ARGS.output.as_ref() {
+        Some(path) => std::fs::write(path, output).map_err(|err| RunError::Output(err.into())),
+        None => {
+            print!("{output}");
+            Ok(())
+        }
+    }
+}
+
+/**
+Writes every problem to its own file in the directory, so that a local problem archive can be built up
+incrementally. Problem files which already exist are skipped, unless they are to be overwritten.
+*/
+fn write_problem_files(dir: &Path, date: &str, problems: Vec<PotdInfo>) -> Result<(), RunError> {
+    if !ARGS.dry_run {
+        std::fs::create_dir_all(dir).map_err(|err| RunError::Output(err.into()))?;
+    }
+
+    let (mut written, mut skipped) = (0, 0);
+    for problem in problems {
+        let path = dir.join(problem_file_name(&problem, ARGS.format));
+        if !ARGS.overwrite && path.exists() {
+            info!(path = %path.display(), "skipping existing problem file");
+            skipped += 1;
+            continue;
+        }
+
+        let document = PotdDocument {
+            potd: DatedPotd {
+                date: date.to_string(),
+                potd: problem,
+            },
+        };
+        let output = serialize(&document, [&document.potd.potd])?;
+        match ARGS.dry_run {
+            true => eprint!("# {}\n{output}", path.display()),
+            false => std::fs::write(&path, output).map_err(|err| RunError::Output(err.into()))?,
+        }
+        written += 1;
+    }
+
+    info!(dir = %dir.display(), written, skipped, "wrote problem files");
+    Ok(())
+}
+
+/// Writes out the problems, either into the SQLite database or as the serialized document.
+fn write_problems<'a, T: serde::Serialize>(
+    date: &str,
+    document: &T,
+    problems: impl IntoIterator<Item = &'a PotdInfo>,
+) -> Result<(), RunError> {
+    match &ARGS.sqlite {
+        Some(path) => {
+            let upserted = upsert_problems(path, date, problems).map_err(RunError::Output)?;
+            info!(path = %path.display(), upserted, "upserted problems");
+            Ok(())
+        }
+        None => write_output(serialize(document, problems)?),
+    }
+}
+
+/// Reads the POTD history from the output file, migrating it if it holds a single POTD.
+fn read_history(path: &str) -> Result<Vec<DatedPotd>, RunError> {
+    // First run would not have any history yet
+    let content = std::fs::read_to_string(path).unwrap_or_default();
+    parse_history(&content, ARGS.format).map_err(RunError::Output)
+}
+
+/// Session cookies of the cookies file, or else of the LEETCODE_COOKIES env variable, if any.
+fn read_session_cookies(args: &Args) -> Result<Option<SessionCookies>, String> {
+    let cookies = match &args.cookies_file {
+        Some(path) => std::fs::read_to_string(path).map_err(|err| {
+            format!(
+                "could not read the cookies file '{}' ( {err} )",
+                path.display()
+            )
+        })?,
+        None => match std::env::var("LEETCODE_COOKIES") {
+            Ok(cookies) => cookies,
+            Err(_) => return Ok(None),
+        },
+    };
+
+    cookies
+        .parse()
+        .map(Some)
+        .map_err(|err| format!("invalid session cookies, {err}"))
+}
+
+/// Main Scraping function.
+async fn scraping(driver: &WebDriver) -> Result<(), RunError> {
+    // Session is signed in on every attempt, as an attempt might have failed on signing in itself
+    if let Some(cookies) = SESSION_COOKIES.get() {
+        add_session_cookies(driver, &ARGS.domain, cookies).await?;
+    }
+
+    let options = ScrapeOptions {
+        domain: Some(ARGS.domain.clone()),
+        selectors: ARGS.selectors.clone().unwrap_or_default(),
+        headful: *HEADFUL,
+        with_tags: ARGS.with_tags,
+        with_description: ARGS.with_description,
+        with_constraints: ARGS.with_constraints,
+        with_examples: ARGS.with_examples,
+        with_votes: ARGS.with_votes,
+        with_solution_count: ARGS.with_solution_count,
+        with_companies: ARGS.with_companies,
+        with_similar: ARGS.with_similar,
+        with_templates: ARGS.with_templates,
+        template_lang: ARGS.lang.clone(),
+        plain_text: ARGS.plain_text,
+        max_pages: ARGS.max_pages,
+        timeout: ARGS.timeout_secs.map(Duration::from_secs),
+        poll_interval: ARGS.poll_interval_ms.map(Duration::from_millis),
+        authenticated: SESSION_COOKIES.get().is_some(),
+        interrupted: INTERRUPTED.clone(),
+    };
+    let date = ARGS.timezone.today(&ARGS.date_format);
+
+    if let Some(contest) = &ARGS.contest {
+        let document = ContestDocument {
+            contest: contest.clone(),
+            date,
+            problems: scrape_contest(driver, contest, &options).await?,
+        };
+        write_problems(&document.date, &document, &document.problems)?;
+    } else if ARGS.all {
+        let problems = scrape_all(driver, &options).await?;
+        match &ARGS.output_dir {
+            Some(dir) => write_problem_files(dir, &date, problems)?,
+            None => {
+                let document = ProblemsDocument { date, problems };
+                write_problems(&document.date, &document, &document.problems)?;
+            }
+        }
+
+        // Problems scraped before the interrupt are written, but the run still didn't complete
+        if INTERRUPTED.load(Ordering::Relaxed) {
+            return Err(RunError::Interrupted);
+        }
+    } else {
+        let potd = DatedPotd {
+            date,
+            potd: scrape_potd(driver, &options).await?,
+        };
+
+        if ARGS.append {
+            let path = ARGS
+                .output
+                .as_ref()
+                .expect("for an output path to append to");
+
+            // Only a single POTD is recorded per date
+            let mut history = read_history(path)?;
+            if history.iter().all(|entry| entry.date != potd.date) {
+                history.push(potd);
+            }
+
+            if let Some(since) = &ARGS.since {
+                let pruned =
+                    prune_history(&mut history, since.date(&ARGS.timezone), &ARGS.date_format);
+                info!(
+                    pruned,
+                    "pruned the history entries older than the since date"
+                );
+            }
+
+            let document = HistoryDocument { potd: history };
+            write_output(serialize(
+                &document,
+                document.potd.iter().map(|entry| &entry.potd),
+            )?)?;
+        } else {
+            let document = PotdDocument { potd };
+            write_problems(&document.potd.date, &document, [&document.potd.potd])?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs the scraping, retrying from scratch with an exponential backoff until attempts are exhausted.
+async fn scraping_with_retries(driver: &WebDriver) -> Result<(), RunError> {
+    let mut res = scraping(driver).await;
+
+    for attempt in 1..ARGS.attempts {
+        // Output failures would only fail again
+        let Err(err @ RunError::Scrape(_)) = &res else {
+            break;
+        };
+        if INTERRUPTED.load(Ordering::Relaxed) {
+            break;
+        }
+
+        let delay = Duration::from_millis(ARGS.retry_delay_ms)
+            .saturating_mul(2u32.saturating_pow(attempt - 1))
+            .min(MAX_RETRY_DELAY);
+        warn!(
+            "attempt {attempt}/{} failed ( {err} ), retrying in {delay:?}",
+            ARGS.attempts
+        );
+        tokio::time::sleep(delay).await;
+
+        res = scraping(driver).await;
+    }
+
+    res
+}
+
+/**
+Resolves once the run is to be aborted on Ctrl-C. Scraping the whole problemset is only stopped
+from paginating on the first press, so that the problems scraped so far are written, and is
+aborted on the second press.
+*/
+async fn interrupts() {
+    if tokio::signal::ctrl_c().await.is_err() {
+        return std::future::pending().await;
+    }
+    INTERRUPTED.store(true, Ordering::Relaxed);
+
+    if ARGS.all {
+        warn!("interrupted, writing the problems scraped so far ( press Ctrl-C again to abort )");
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+/**
+Captures what the page looked like when scraping failed, as a screenshot and the html source.
+Capture failures are only logged, so that they don't hide the original scraping error.
+*/
+async fn capture_failure(driver: &WebDriver) {
+    if let Some(path) = &ARGS.failure_screenshot {
+        match driver.screenshot(path).await {
+            Ok(()) => info!(path = %path.display(), "saved failure screenshot"),
+            Err(err) => warn!("could not save failure screenshot ( {err} )"),
+        }
+    }
+
+    if let Some(path) = &ARGS.failure_source {
+        let source = driver.source().await.map_err(|err| err.to_string());
+        match source.and_then(|source| std::fs::write(path, source).map_err(|err| err.to_string()))
+        {
+            Ok(()) => info!(path = %path.display(), "saved failure page source"),
+            Err(err) => warn!("could not save failure page source ( {err} )"),
+        }
+    }
+}
+
+/// Session capabilities of the browser, running it headless unless headful.
+fn capabilities(
+    browser: &Browser,
+    headful: bool,
+    shared: &SharedArgs,
+) -> WebDriverResult<Capabilities> {
+    let mut caps: Capabilities = match browser {
+        Browser::Chrome => {
+            let mut caps = ChromeCapabilities::new();
+            if !headful {
+                caps.set_headless()?;
+            }
+            if let Some(user_agent) = &shared.user_agent {
+                caps.add_chrome_arg(&format!("--user-agent={user_agent}"))?;
+            }
+            caps.into()
+        }
+        Browser::Firefox => {
+            let mut caps = FirefoxCapabilities::new();
+            if !headful {
+                caps.set_headless()?;
+            }
+            if let Some(user_agent) = &shared.user_agent {
+                let mut preferences = FirefoxPreferences::new();
+                preferences.set_user_agent(user_agent.clone())?;
+                caps.set_preferences(preferences)?;
+            }
+            caps.into()
+        }
+    };
+
+    if let Some(proxy) = &shared.proxy {
+        caps.set_proxy(browser_proxy(proxy)?)?;
+    }
+    Ok(caps)
+}
+
+/// Browser proxy of the proxy url, which routes every request through its host.
+fn browser_proxy(proxy: &str) -> WebDriverResult<Proxy> {
+    let url =
+        reqwest::Url::parse(proxy).map_err(|err| WebDriverError::CustomError(err.to_string()))?;
+    let address = format!(
+        "{}:{}",
+        url.host_str().unwrap_or_default(),
+        url.port_or_known_default().unwrap_or(1080)
+    );
+
+    Ok(match url.scheme() {
+        "socks5" | "socks5h" => Proxy::Manual {
+            ftp_proxy: None,
+            http_proxy: None,
+            ssl_proxy: None,
+            socks_proxy: Some(address),
+            socks_version: Some(5),
+            socks_username: None,
+            socks_password: None,
+            no_proxy: None,
+        },
+        _ => Proxy::Manual {
+            ftp_proxy: None,
+            http_proxy: Some(address.clone()),
+            ssl_proxy: Some(address),
+            socks_proxy: None,
+            socks_version: None,
+            socks_username: None,
+            socks_password: None,
+            no_proxy: None,
+        },
+    })
+}
+
+/// Sets up the WebDriver session and runs the scraping on it.
+async fn run_session(shared: &SharedArgs) -> Result<(), RunError> {
+    let caps = capabilities(&ARGS.browser, *HEADFUL, shared).map_err(RunError::Connect)?;
+
+    // Validate the endpoint beforehand, rather than failing on a session request to nowhere
+    let webdriver_url =
+        reqwest::Url::parse(&ARGS.webdriver_url).map_err(|err| RunError::Connect(err.into()))?;
+    if !["http", "https"].contains(&webdriver_url.scheme()) {
+        return Err(RunError::Connect(WebDriverError::CustomError(format!(
+            "webdriver url '{webdriver_url}' should be an http(s) url"
+        ))));
+    }
+
+    let driver = WebDriver::new(webdriver_url.as_str(), caps)
+        .await
+        .map_err(RunError::Connect)?;
+
+    // Health check tells a WebDriver which isn't running apart from a page that can't be scraped
+    if ARGS.check {
+        let res = driver.goto("about:blank").await;
+        driver.quit().await.map_err(RunError::Connect)?;
+        res.map_err(RunError::Connect)?;
+
+        println!("OK: webdriver at {webdriver_url} is reachable");
+        return Ok(());
+    }
+
+    // Browser would be left running if the session was not quit on an interrupt
+    let res = tokio::select! {
+        res = scraping_with_retries(&driver) => res,
+        () = interrupts() => Err(RunError::Interrupted),
+    };
+    if res.is_err() && !matches!(res, Err(RunError::Interrupted)) {
+        capture_failure(&driver).await;
+    }
+
+    // Perform a Quit operation even if scraping fails
+    driver.quit().await.map_err(RunError::Connect)?;
+
+    res
+}
+
+/// Runs the LeetCode scraper, with the options shared by both scrapers routed to the browser.
+pub async fn run(mut args: Args, shared: SharedArgs) -> ExitCode {
+    // Arguments are validated upfront, before any WebDriver session is requested
+    args.output.clone_from(&shared.output);
+    if let Some(format) = &shared.format {
+        args.format = match parse_format(format) {
+            Ok(format) => format,
+            Err(err) => clap::Error::raw(ErrorKind::InvalidValue, format!("{err}\n")).exit(),
+        };
+    }
+
+    // Shared options given before the subcommand are only set once the subcommand is parsed,
+    // and so are checked against the LeetCode ones here rather than by clap
+    let output_conflict = match (&args.output_dir, &args.sqlite) {
+        _ if args.output.is_none() => None,
+        (Some(_), _) => Some("--output-dir"),
+        (_, Some(_)) => Some("--sqlite"),
+        (None, None) => None,
+    };
+    if let Some(arg) = output_conflict {
+        clap::Error::raw(
+            ErrorKind::ArgumentConflict,
+            format!("`{arg}` can't be used with `--output`\n"),
+        )
+        .exit();
+    }
+    if args.append && args.output.is_none() {
+        clap::Error::raw(
+            ErrorKind::MissingRequiredArgument,
+            "`--append` requires `--output`\n",
+        )
+        .exit();
+    }
+
+    // CSV tables can't be read back into a history
+    if args.append && matches!(args.format, OutputFormat::Csv) {
+        clap::Error::raw(
+            ErrorKind::ArgumentConflict,
+            "`--append` can't be used with `--format csv`\n",
+        )
+        .exit();
+    }
+    match read_session_cookies(&args) {
+        Ok(Some(cookies)) => {
+            if SESSION_COOKIES.set(cookies).is_err() {
+                panic!("session cookies to only be set once");
+            }
+        }
+        Ok(None) if args.with_companies => clap::Error::raw(
+            ErrorKind::MissingRequiredArgument,
+            "`--with-companies` requires the session cookies of a premium account ( `--cookies-file` or LEETCODE_COOKIES )\n",
+        )
+        .exit(),
+        Ok(None) => {}
+        Err(err) => clap::Error::raw(ErrorKind::InvalidValue, format!("{err}\n")).exit(),
+    }
+    if PARSED_ARGS.set(args).is_err() {
+        panic!("arguments to only be set once");
+    }
+
+    // Logs are filtered with RUST_LOG, defaulting to the scraping steps when headful or on a dry run
+    let default_filter = if *HEADFUL || ARGS.dry_run {
+        "info"
+    } else {
+        "warn"
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_filter)),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    match run_session(&shared).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(err.exit_code())
+        }
+    }
+}
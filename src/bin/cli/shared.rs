@@ -0,0 +1,31 @@
+/// Options shared by both scrapers, given before or after the subcommand of the `scraprs` binary.
+#[derive(clap::Args)]
+pub struct SharedArgs {
+    /// Proxy requests are routed through, as an http(s) or socks5 url, instead of the HTTP_PROXY / HTTPS_PROXY one
+    #[arg(long, global = true, value_parser = parse_proxy)]
+    pub proxy: Option<String>,
+
+    /// User agent requests are made with, instead of the crawler one for Wikipedia and the browser one for LeetCode
+    #[arg(long, global = true, env = "SCRAPER_UA")]
+    pub user_agent: Option<String>,
+
+    /// Serialization format of the output, one of toml ( default ), json, yaml or csv for LeetCode, and debug ( default ), dot, edgelist, graphml, json or toml for Wikipedia
+    #[arg(long, global = true)]
+    pub format: Option<String>,
+
+    /// Output file path, the output is printed to stdout when not given
+    #[arg(long, short, global = true)]
+    pub output: Option<String>,
+}
+
+/// Proxy url parser, failing fast on a malformed url rather than on the first request.
+pub fn parse_proxy(proxy: &str) -> Result<String, String> {
+    let url = reqwest::Url::parse(proxy).map_err(|err| format!("malformed proxy url ( {err} )"))?;
+    match url.scheme() {
+        "http" | "https" | "socks5" | "socks5h" if url.has_host() => Ok(proxy.to_string()),
+        "http" | "https" | "socks5" | "socks5h" => Err("expected a proxy host".to_string()),
+        scheme => Err(format!(
+            "unsupported proxy scheme '{scheme}', expected one of http, https, socks5 or socks5h"
+        )),
+    }
+}
@@ -0,0 +1,677 @@
+/*!
+Wikipedia page scraper for fetching all relevant links and connected wiki pages.
+The script uses relative wiki paths, as connected page links are also relative.
+
+Usage :
+
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)"
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --depth 2
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" "/wiki/Go_(programming_language)"
+    - ./target/release/wikipedia_links "Rust (programming language)"
+    - ./target/release/wikipedia_links --input-file seeds.txt
+    - ./target/release/wikipedia_links "/wiki/Rust_(langage)" --lang fr
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --titles
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --counts --top 5
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --summary
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --external --domain github.com
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --interwiki
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --depth 2 --cache-dir .cache
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --titles --verify-links
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --depth 3 --limit 50
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --depth 2 --include-pattern "language" --exclude-pattern "^List_of"
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --depth 3 --limit 1000 --checkpoint crawl.json
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --depth 3 --limit 1000 --checkpoint crawl.json --resume
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --depth 2 --format json --with-categories
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --depth 2 --format dot | dot -Tsvg
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --depth 2 --format graphml > rust.graphml
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --depth 2 --format edgelist | cut -f2 | sort | uniq -c
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --depth 2 --stats --pagerank
+    - ./target/release/wikipedia_links "/wiki/Rust_(programming_language)" --proxy socks5h://localhost:9050
+    - ./target/release/scraprs wikipedia "/wiki/Rust_(programming_language)" --depth 2
+*/
+
+use crate::shared::{parse_proxy, SharedArgs};
+use clap::{builder::RangedU64ValueParser, error::ErrorKind, ValueEnum};
+use regex::Regex;
+use scraprs::wiki::{
+    base_wiki_url, build_client, crawl, degree_stats, fetch_external_links, fetch_interwiki_links,
+    fetch_many, fetch_wiki_link_counts, fetch_wiki_links, fetch_wiki_summary, link_graph,
+    normalize_wiki_ref, parse_wiki_ref_list, to_dot, to_edgelist, to_graphml, to_json,
+    verify_links, CrawlOptions, CrawlState, CrawledPage, Fetcher, LinkFilter, LinkScope, Namespace,
+    PageCache, PoolOptions, RateLimiter, DEFAULT_CACHE_TTL, DEFAULT_CONCURRENCY,
+    DEFAULT_POOL_IDLE_TIMEOUT, DEFAULT_POOL_MAX_IDLE_PER_HOST, DEFAULT_REQUEST_DELAY,
+    DEFAULT_REQUEST_TIMEOUT, DEFAULT_USER_AGENT, MAX_CRAWL_PAGES,
+};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::env;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufWriter, IsTerminal, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tracing::{info, warn};
+use tracing_subscriber::EnvFilter;
+
+/// Fetches the links of a Wikipedia page, and crawls the pages connected to it.
+#[derive(clap::Args)]
+pub struct Args {
+    /// Pages to start from, as relative wiki paths ( /wiki/Rust ), article titles or page urls
+    #[arg(required_unless_present = "input_file", value_parser = parse_start_ref)]
+    start_refs: Vec<String>,
+
+    /// File of newline-separated pages to start from as well, where blank lines and `#` comments are ignored
+    #[arg(long)]
+    input_file: Option<PathBuf>,
+
+    /// Wikipedia language, used as the subdomain
+    #[arg(long, env = "WIKI_LANG", default_value = "en", value_parser = parse_lang)]
+    lang: String,
+
+    /// Pages crawled, as hops away from the start page, where 1 fetches only the start page itself
+    #[arg(long, default_value_t = 1, value_parser = at_least_one())]
+    depth: usize,
+
+    /// Pages fetched in a crawl at most, whatever the depth, as a safety valve for branchy crawls
+    #[arg(long, default_value_t = MAX_CRAWL_PAGES, value_parser = at_least_one())]
+    limit: usize,
+
+    /// Only crawl into the articles whose name ( eg. Rust_(programming_language) ) matches any of these regexes
+    #[arg(long, value_parser = Regex::new)]
+    include_pattern: Vec<Regex>,
+
+    /// Never crawl into the articles whose name matches any of these regexes, even if included
+    #[arg(long, value_parser = Regex::new)]
+    exclude_pattern: Vec<Regex>,
+
+    /// State file the crawl is checkpointed to every few seconds, so that it can be resumed once interrupted
+    #[arg(long, conflicts_with_all = ["titles", "counts", "summary", "external", "interwiki"])]
+    checkpoint: Option<PathBuf>,
+
+    /// Resume the crawl checkpointed to the `--checkpoint` state file, rather than starting over
+    #[arg(long, requires = "checkpoint")]
+    resume: bool,
+
+    // Output format of the crawled link graph, set from the shared `--format`
+    #[arg(skip = Format::Debug)]
+    format: Format,
+
+    /// Print the in-degree and out-degree of every page of the link graph instead, as a table or as JSON with `--format json`
+    #[arg(long, conflicts_with_all = ["titles", "counts", "summary", "external", "interwiki"])]
+    stats: bool,
+
+    /// Rank the pages with PageRank as well, in the `--stats` table
+    #[arg(long, requires = "stats")]
+    pagerank: bool,
+
+    /// Pages fetched at the same time, kept low by default to be polite to Wikipedia
+    #[arg(long, default_value_t = DEFAULT_CONCURRENCY, value_parser = at_least_one())]
+    concurrency: usize,
+
+    /// Milliseconds between any two requests, even when fetched concurrently, where 0 disables the delay
+    #[arg(long, default_value_t = DEFAULT_REQUEST_DELAY.as_millis() as u64)]
+    delay_ms: u64,
+
+    /// Seconds after which a request is given up on, so that a hung connection doesn't stall the crawl
+    #[arg(long, default_value_t = DEFAULT_REQUEST_TIMEOUT.as_secs(), value_parser = clap::value_parser!(u64).range(1..))]
+    request_timeout_secs: u64,
+
+    /// Idle connections kept open to Wikipedia for reuse, which should be at least the concurrency
+    #[arg(long, env = "SCRAPER_POOL_MAX_IDLE", default_value_t = DEFAULT_POOL_MAX_IDLE_PER_HOST)]
+    pool_max_idle_per_host: usize,
+
+    /// Seconds after which idle connections are closed, where 0 keeps them open for the whole run
+    #[arg(long, env = "SCRAPER_POOL_IDLE_TIMEOUT_SECS", default_value_t = DEFAULT_POOL_IDLE_TIMEOUT.as_secs())]
+    pool_idle_timeout_secs: u64,
+
+    /// Times a timed out request is retried, before the page is skipped
+    #[arg(long, default_value_t = 2)]
+    timeout_retries: u32,
+
+    /// Times a rate limited ( 429 ) or failing ( 500 to 503 ) request is retried with an exponential backoff, honoring Retry-After
+    #[arg(long, default_value_t = 3)]
+    max_retries: u32,
+
+    /// Links kept per page, in document order, which keeps crawls of list pages manageable
+    #[arg(long)]
+    max_links: Option<usize>,
+
+    /// Directory fetched pages are cached in
+    #[arg(long, env = "SCRAPER_CACHE_DIR")]
+    cache_dir: Option<PathBuf>,
+
+    /// Seconds after which cached pages are refetched
+    #[arg(long, default_value_t = DEFAULT_CACHE_TTL.as_secs())]
+    cache_ttl_secs: u64,
+
+    /// Bypass the page cache
+    #[arg(long)]
+    no_cache: bool,
+
+    /// List the links of only the start page, along with their titles and namespaces
+    #[arg(long)]
+    titles: bool,
+
+    /// List how many times each article is linked to from only the start page, most linked first
+    #[arg(long, conflicts_with = "titles")]
+    counts: bool,
+
+    /// Articles listed with `--counts`
+    #[arg(long, default_value_t = 10, requires = "counts")]
+    top: usize,
+
+    /// Print the first-paragraph summary of only the start page, which is empty for disambiguation pages
+    #[arg(long, conflicts_with_all = ["titles", "counts"])]
+    summary: bool,
+
+    /// Only log errors, and skip printing the links in the default debug format
+    #[arg(long, short, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Log more of the crawl, where -v logs every request and selector, and -vv traces the http client as well
+    #[arg(long, short, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// List the categories of every crawled page, in the JSON and TOML formats
+    #[arg(long)]
+    with_categories: bool,
+
+    /// List the hidden maintenance categories of every crawled page as well
+    #[arg(long, requires = "with_categories")]
+    hidden_categories: bool,
+
+    /// Record the response metadata of every crawled page ( final url, status, content length and fetch duration ), in the JSON and TOML formats
+    #[arg(long)]
+    with_meta: bool,
+
+    /// Check that every listed link resolves with a HEAD request, dropping the dead ones, which is slower and so opt-in
+    #[arg(long, conflicts_with_all = ["counts", "summary", "external", "interwiki", "include_redlinks"])]
+    verify_links: bool,
+
+    /// List the external links cited by only the start page, rather than its wiki links
+    #[arg(long, conflicts_with_all = ["titles", "counts", "summary"])]
+    external: bool,
+
+    /// Only list the external links to this domain or its subdomains ( eg. github.com )
+    #[arg(long, requires = "external")]
+    domain: Option<String>,
+
+    /// List the links to only the start page in other languages, along with their language codes
+    #[arg(long, conflicts_with_all = ["titles", "counts", "summary", "external"])]
+    interwiki: bool,
+
+    /// List the red links ( to articles which don't exist yet ) as well, which are never crawled
+    #[arg(long, requires = "titles")]
+    include_redlinks: bool,
+
+    /// Keep the section fragments of the listed links, rather than collapsing them to their article
+    #[arg(long, requires = "titles")]
+    keep_fragments: bool,
+
+    /// Part of the page the links are collected from, as paragraphs, lists ( along with the paragraphs ) or the whole body
+    #[arg(long, default_value = "paragraphs", conflicts_with = "counts")]
+    link_scope: LinkScope,
+
+    /// Only keep the links into these namespaces ( eg. main,category or Wikipedia ), instead of only the articles
+    #[arg(long, value_delimiter = ',', conflicts_with = "counts")]
+    namespace: Vec<Namespace>,
+}
+
+#[derive(Clone, ValueEnum)]
+enum Format {
+    /// Debug representation of the page to links map
+    Debug,
+    /// Graphviz DOT graph
+    Dot,
+    /// Deduplicated `source<TAB>target` lines, one per link, sorted for reproducible output
+    Edgelist,
+    /// GraphML graph, for network analysis tools
+    Graphml,
+    /// JSON object of the page to links map, sorted by page, or of the pages with their depth for a crawl
+    Json,
+    /// TOML table of the page to links map, or of the pages with their depth for a crawl
+    Toml,
+}
+
+// Set on the first Ctrl-C, so that the crawl stops and the links fetched so far are still printed
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Ctrl-C handler, interrupting the crawl on the first press and aborting on the second.
+async fn handle_interrupts() {
+    if tokio::signal::ctrl_c().await.is_err() {
+        return;
+    }
+    INTERRUPTED.store(true, Ordering::Relaxed);
+    warn!("interrupted, finishing the pages in flight ( press Ctrl-C again to abort )");
+
+    if tokio::signal::ctrl_c().await.is_ok() {
+        std::process::exit(130);
+    }
+}
+
+fn at_least_one() -> RangedU64ValueParser<usize> {
+    RangedU64ValueParser::new().range(1..)
+}
+
+/// Start ref parser, normalizing the page reference before starting any crawl.
+fn parse_start_ref(start_ref: &str) -> Result<String, String> {
+    normalize_wiki_ref(start_ref).map_err(|err| err.to_string())
+}
+
+fn parse_lang(lang: &str) -> Result<String, String> {
+    match base_wiki_url(lang) {
+        Ok(_) => Ok(lang.to_string()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Output document of a crawl, keeping the pages under a `pages` key as TOML needs a top-level table.
+#[derive(serde::Serialize)]
+struct CrawlDocument<'a> {
+    pages: &'a [CrawledPage],
+}
+
+/// Output format parser, listing the supported formats on failure.
+fn parse_format(format: &str) -> Result<Format, String> {
+    Format::from_str(format, true).map_err(|_| {
+        format!("invalid format '{format}', expected one of debug, dot, edgelist, graphml, json or toml")
+    })
+}
+
+/// Checks if the url is on the domain, or any of its subdomains.
+fn is_on_domain(url: &str, domain: &str) -> bool {
+    let domain = domain.trim_start_matches('.').to_lowercase();
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_lowercase))
+        .is_some_and(|host| host == domain || host.ends_with(&format!(".{domain}")))
+}
+
+fn print_graph(
+    out: &mut impl Write,
+    link_records: &HashMap<String, Vec<String>>,
+    format: &Format,
+    quiet: bool,
+) -> io::Result<()> {
+    match format {
+        // Debug representation is only meant to be read, and so is left out when quiet
+        Format::Debug if quiet => Ok(()),
+        Format::Debug => writeln!(out, "{:#?}", link_records),
+        Format::Dot => write!(out, "{}", to_dot(link_records)),
+        Format::Edgelist => write!(out, "{}", to_edgelist(link_records)),
+        Format::Graphml => write!(out, "{}", to_graphml(link_records)),
+        Format::Json => writeln!(out, "{}", to_json(link_records)),
+        Format::Toml => write!(
+            out,
+            "{}",
+            toml::to_string(&link_records.iter().collect::<BTreeMap<_, _>>())
+                .expect("to serialize a map of strings")
+        ),
+    }
+}
+
+/// Prints the degree statistics of the link graph, as JSON in the JSON format, or else as a table.
+fn print_stats(
+    out: &mut impl Write,
+    link_records: &HashMap<String, Vec<String>>,
+    format: &Format,
+    with_pagerank: bool,
+) -> Result<(), Box<dyn Error>> {
+    let stats = degree_stats(link_records, with_pagerank);
+    if let Format::Json = format {
+        writeln!(out, "{}", serde_json::to_string_pretty(&stats)?)?;
+        return Ok(());
+    }
+
+    match with_pagerank {
+        true => writeln!(out, "in\tout\tpagerank\tpage")?,
+        false => writeln!(out, "in\tout\tpage")?,
+    }
+    for node in stats {
+        match node.pagerank {
+            Some(rank) => writeln!(
+                out,
+                "{}\t{}\t{rank:.6}\t{}",
+                node.in_degree, node.out_degree, node.node
+            )?,
+            None => writeln!(
+                out,
+                "{}\t{}\t{}",
+                node.in_degree, node.out_degree, node.node
+            )?,
+        }
+    }
+    Ok(())
+}
+
+/// Prints the crawled pages, along with their depth in the JSON and TOML formats.
+fn print_crawl(
+    out: &mut impl Write,
+    pages: &[CrawledPage],
+    format: &Format,
+    quiet: bool,
+) -> Result<(), Box<dyn Error>> {
+    match format {
+        Format::Json => writeln!(
+            out,
+            "{}",
+            serde_json::to_string_pretty(&CrawlDocument { pages })?
+        )?,
+        Format::Toml => write!(out, "{}", toml::to_string(&CrawlDocument { pages })?)?,
+        format => print_graph(out, &link_graph(pages), format, quiet)?,
+    }
+    Ok(())
+}
+
+/// Dead links among the links, as verified with HEAD requests, reporting how many were found.
+async fn dead_links<'a>(
+    fetcher: &Fetcher,
+    links: impl IntoIterator<Item = &'a String>,
+    concurrency: usize,
+) -> HashSet<String> {
+    let dead = verify_links(fetcher, links, concurrency).await;
+    info!("found {} dead links", dead.len());
+    dead.into_iter().collect()
+}
+
+/// Runs the Wikipedia scraper, with the options shared by both scrapers routed to the HTTP client.
+pub async fn run(mut args: Args, shared: SharedArgs) -> Result<(), Box<dyn Error>> {
+    if let Some(format) = &shared.format {
+        args.format = match parse_format(format) {
+            Ok(format) => format,
+            Err(err) => clap::Error::raw(ErrorKind::InvalidValue, format!("{err}\n")).exit(),
+        };
+    }
+
+    // Logs are filtered with `--quiet` / `--verbose`, or else with RUST_LOG, defaulting to the skipped pages
+    let filter = match (args.quiet, args.verbose) {
+        (true, _) => EnvFilter::new("error"),
+        (false, 0) => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        (false, 1) => EnvFilter::new("info,scraprs=debug,wikipedia_links=debug"),
+        (false, _) => EnvFilter::new("trace"),
+    };
+
+    // Crawl progress is only meant to be watched, and so is left out when logs are not on a terminal
+    let filter = match std::io::stderr().is_terminal() {
+        true => filter,
+        false => filter.add_directive("progress=off".parse()?),
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .init();
+
+    // Pages are cached under `--cache-dir`, unless bypassed with `--no-cache`
+    let cache = args
+        .cache_dir
+        .filter(|_| !args.no_cache)
+        .map(|dir| PageCache {
+            dir,
+            ttl: Duration::from_secs(args.cache_ttl_secs),
+        });
+
+    // User agent can be overridden with `--user-agent` or the SCRAPER_UA env variable
+    let user_agent = shared
+        .user_agent
+        .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string());
+
+    // Proxy env variables are validated as well, since reqwest would silently ignore a malformed one
+    for var in ["HTTPS_PROXY", "https_proxy", "HTTP_PROXY", "http_proxy"] {
+        match env::var(var) {
+            Ok(proxy) if shared.proxy.is_none() && !proxy.is_empty() => {
+                // Proxies without a scheme are taken as http ones, as reqwest does
+                let proxy = match proxy.contains("://") {
+                    true => proxy,
+                    false => format!("http://{proxy}"),
+                };
+                parse_proxy(&proxy).map_err(|err| format!("invalid {var}: {err}"))?;
+            }
+            _ => {}
+        }
+    }
+
+    let fetcher = Fetcher {
+        client: build_client(
+            &user_agent,
+            shared.proxy.as_deref(),
+            Duration::from_secs(args.request_timeout_secs),
+            &PoolOptions {
+                max_idle_per_host: args.pool_max_idle_per_host,
+                idle_timeout: match args.pool_idle_timeout_secs {
+                    0 => None,
+                    secs => Some(Duration::from_secs(secs)),
+                },
+            },
+        )?,
+        base_url: base_wiki_url(&args.lang)?,
+        cache,
+        rate_limiter: match args.delay_ms {
+            0 => None,
+            delay_ms => Some(RateLimiter::new(Duration::from_millis(delay_ms))),
+        },
+        timeout_retries: args.timeout_retries,
+        max_retries: args.max_retries,
+    };
+
+    // Output is written to the shared `--output` file when given, or else printed to stdout
+    let mut out: Box<dyn Write> = match &shared.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path).map_err(|err| {
+            format!("could not create the output file '{path}' ( {err} )")
+        })?)),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    // Only the links of the start pages themselves are fetched for multiple start pages
+    // Seed file pages are started from after the ones given as arguments
+    let mut start_refs = args.start_refs.clone();
+    if let Some(path) = &args.input_file {
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| format!("could not read '{}' ( {err} )", path.display()))?;
+        let (refs, invalid) = parse_wiki_ref_list(&content);
+        for (line, err) in &invalid {
+            warn!(file = %path.display(), line, "skipping invalid page ( {err} )");
+        }
+        start_refs.extend(refs);
+    }
+
+    // Links are only kept in the namespaces asked for, or otherwise only the articles are
+    let namespaces = match args.namespace.is_empty() {
+        true => None,
+        false => Some(args.namespace.iter().cloned().collect::<HashSet<_>>()),
+    };
+
+    let Some(start_ref) = start_refs.first() else {
+        return Err("no valid page to start from".into());
+    };
+    if start_refs.len() > 1 {
+        if args.titles
+            || args.counts
+            || args.summary
+            || args.external
+            || args.interwiki
+            || args.depth > 1
+        {
+            return Err("multiple start pages are only supported with a depth of 1".into());
+        }
+
+        let filter = LinkFilter {
+            namespaces: namespaces.clone(),
+            scope: args.link_scope,
+            max_links: args.max_links,
+            ..LinkFilter::default()
+        };
+        let mut batch = fetch_many(&fetcher, &start_refs, args.concurrency, filter).await;
+        if args.verify_links {
+            let dead = dead_links(&fetcher, batch.links.values().flatten(), args.concurrency).await;
+            for links in batch.links.values_mut() {
+                links.retain(|link| !dead.contains(link));
+            }
+        }
+        match args.stats {
+            true => print_stats(&mut out, &batch.links, &args.format, args.pagerank)?,
+            false => print_graph(&mut out, &batch.links, &args.format, args.quiet)?,
+        }
+
+        for (url_ref, err) in &batch.errors {
+            warn!(page = url_ref, "could not fetch page ( {err} )");
+        }
+        return match batch.errors.len() {
+            0 => Ok(()),
+            failed => Err(format!("{failed} of {} start pages failed", start_refs.len()).into()),
+        };
+    }
+
+    // Link records of only the start page are listed along with their titles
+    if args.titles {
+        let filter = LinkFilter {
+            dedup: true,
+            keep_fragments: args.keep_fragments,
+            include_redlinks: args.include_redlinks,
+            namespaces: namespaces.clone(),
+            scope: args.link_scope,
+            max_links: args.max_links,
+            ..LinkFilter::default()
+        };
+        let mut links = fetch_wiki_links(&fetcher, start_ref, filter).await?;
+        if args.verify_links {
+            let hrefs = links.iter().map(|link| &link.href).collect::<Vec<_>>();
+            let dead = dead_links(&fetcher, hrefs, args.concurrency).await;
+            links.retain(|link| !dead.contains(&link.href));
+        }
+        for link in links {
+            writeln!(
+                out,
+                "{}\t{}\t{}\t{}",
+                link.href, link.title, link.display_text, link.namespace
+            )?;
+        }
+
+        return Ok(());
+    }
+
+    if args.summary {
+        writeln!(out, "{}", fetch_wiki_summary(&fetcher, start_ref).await?)?;
+        return Ok(());
+    }
+
+    if args.external {
+        let page = fetcher.fetch_page(start_ref).await?;
+        for link in fetch_external_links(&page.html)? {
+            if args
+                .domain
+                .as_ref()
+                .is_none_or(|domain| is_on_domain(&link, domain))
+            {
+                writeln!(out, "{link}")?;
+            }
+        }
+
+        return Ok(());
+    }
+
+    // Language links of only the start page are listed in the order of their language codes
+    if args.interwiki {
+        let page = fetcher.fetch_page(start_ref).await?;
+        let links = fetch_interwiki_links(&page.html)
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+        for (lang, url) in links {
+            writeln!(out, "{lang}\t{url}")?;
+        }
+
+        return Ok(());
+    }
+
+    // Most linked articles of only the start page are listed along with their link counts
+    if args.counts {
+        let page = fetcher.fetch_page(start_ref).await?;
+        let mut counts = fetch_wiki_link_counts(&page.html)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        counts.sort_by(|(href_a, count_a), (href_b, count_b)| {
+            count_b.cmp(count_a).then_with(|| href_a.cmp(href_b))
+        });
+
+        for (href, count) in counts.into_iter().take(args.top) {
+            writeln!(out, "{count}\t{href}")?;
+        }
+
+        return Ok(());
+    }
+
+    let filter = LinkFilter {
+        dedup: true,
+        namespaces,
+        scope: args.link_scope,
+        max_links: args.max_links,
+        ..LinkFilter::default()
+    };
+    let resume = match (&args.checkpoint, args.resume) {
+        (Some(path), true) => Some(CrawlState::load(path).await.map_err(|err| {
+            format!(
+                "could not load the checkpoint '{}' ( {err} )",
+                path.display()
+            )
+        })?),
+        _ => None,
+    };
+    let options = CrawlOptions {
+        depth: args.depth,
+        concurrency: args.concurrency,
+        filter,
+        limit: args.limit,
+        include_patterns: args.include_pattern.clone(),
+        exclude_patterns: args.exclude_pattern.clone(),
+        checkpoint: args.checkpoint.clone(),
+        resume,
+    };
+    tokio::spawn(handle_interrupts());
+    let mut pages = crawl(&fetcher, start_ref, options, &INTERRUPTED).await?;
+
+    // Crawled pages did resolve, and so only the links to the pages which were not crawled are verified
+    if args.verify_links {
+        let crawled = pages
+            .iter()
+            .map(|page| page.page.as_str())
+            .collect::<HashSet<_>>();
+        let uncrawled = pages
+            .iter()
+            .flat_map(|page| &page.links)
+            .filter(|link| !crawled.contains(link.as_str()))
+            .collect::<Vec<_>>();
+        let dead = dead_links(&fetcher, uncrawled, args.concurrency).await;
+        for page in &mut pages {
+            page.links.retain(|link| !dead.contains(link));
+        }
+    }
+
+    // Categories and response metadata are always collected, but only listed on request
+    for page in &mut pages {
+        if !args.with_meta {
+            page.meta = None;
+        }
+        if !args.with_categories {
+            page.categories.clear();
+        }
+        if !args.hidden_categories {
+            page.hidden_categories.clear();
+        }
+    }
+    match args.stats {
+        true => print_stats(&mut out, &link_graph(&pages), &args.format, args.pagerank)?,
+        false => print_crawl(&mut out, &pages, &args.format, args.quiet)?,
+    }
+
+    match (INTERRUPTED.load(Ordering::Relaxed), &args.checkpoint) {
+        (true, Some(path)) => Err(format!(
+            "crawl was interrupted, only the pages fetched so far were printed \
+             ( resume it with --checkpoint {} --resume )",
+            path.display()
+        )
+        .into()),
+        (true, None) => {
+            Err("crawl was interrupted, only the pages fetched so far were printed".into())
+        }
+        (false, _) => Ok(()),
+    }
+}
@@ -0,0 +1,58 @@
+/*!
+Single entry point for both scrapers, with the options shared by them ( format, output, proxy and user agent )
+given at the top level.
+
+Usage :
+
+    - ./target/release/scraprs --output potd.toml leetcode --with-tags
+    - ./target/release/scraprs --format json --output rust.json wikipedia "/wiki/Rust_(programming_language)" --depth 2
+    - ./target/release/scraprs wikipedia "/wiki/Rust_(programming_language)" --depth 2
+    - ./target/release/scraprs --proxy socks5h://localhost:9050 wikipedia "Rust (programming language)"
+*/
+
+#[path = "cli/leetcode.rs"]
+mod leetcode;
+#[path = "cli/shared.rs"]
+mod shared;
+#[path = "cli/wikipedia.rs"]
+mod wikipedia;
+
+use clap::{Parser, Subcommand};
+use std::process::ExitCode;
+
+/// Scrapes LeetCode problems and Wikipedia links.
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(flatten)]
+    shared: shared::SharedArgs,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Scrapes the LeetCode problem of the day ( POTD ), the whole problemset or a contest, through a WebDriver session
+    #[command(after_help = leetcode::EXIT_CODES)]
+    Leetcode(Box<leetcode::Args>),
+
+    /// Fetches the links of a Wikipedia page, and crawls the pages connected to it
+    Wikipedia(Box<wikipedia::Args>),
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Leetcode(args) => leetcode::run(*args, cli.shared).await,
+        // Errors are reported as the `wikipedia_links` binary reports them
+        Command::Wikipedia(args) => match wikipedia::run(*args, cli.shared).await {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(err) => {
+                eprintln!("Error: {err:?}");
+                ExitCode::FAILURE
+            }
+        },
+    }
+}
@@ -0,0 +1,183 @@
+use super::{
+    scrape::{css, scrape_problem_details, step},
+    selector, Difficulty, PotdInfo, ScrapeError, ScrapeOptions,
+};
+use scraper::Html;
+use std::collections::BTreeMap;
+use thirtyfour::{prelude::ElementQueryable, WebDriver};
+use tracing::{info, instrument};
+
+// Contest page lists its problems apart from the problemset table, along with the points of each one
+static CONTEST_QUESTION_LIST: &str = "ul.contest-question-list";
+static CONTEST_QUESTION: &str = "ul.contest-question-list > li";
+static CONTEST_QUESTION_ANCHOR: &str = "a[href*='/problems/']";
+static POINTS_BADGE: &str = ".badge";
+
+// Contest page doesn't show the number, difficulty or acceptance of its problems, and so they are
+// asked of the same GraphQL endpoint that the problem page loads them from
+static QUESTION_SCRIPT: &str = r#"
+    const [slug, done] = arguments;
+    fetch("/graphql", {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({
+            query: "query($slug: String!) { question(titleSlug: $slug) { questionFrontendId difficulty isPaidOnly stats } }",
+            variables: { slug },
+        }),
+    })
+        .then((response) => response.json())
+        .then((body) => done(body?.data?.question ?? null))
+        .catch(() => done(null));
+"#;
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Question {
+    question_frontend_id: String,
+    difficulty: String,
+    is_paid_only: bool,
+    // Stats are a JSON document of their own, holding the acceptance rate ( eg. "acRate": "52.3%" )
+    stats: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QuestionStats {
+    ac_rate: String,
+}
+
+/// Problem listed on a contest page, along with the points it is worth.
+#[derive(Debug, PartialEq)]
+pub struct ContestQuestion {
+    pub slug: String,
+    pub name: String,
+    pub points: u32,
+}
+
+/**
+Contest page parsing function, on the html of the page ( or just its problem list ).
+Returns the problems in the order they are listed in, which is the order of their difficulty.
+*/
+pub fn parse_contest_html(html: &str) -> Result<Vec<ContestQuestion>, ScrapeError> {
+    let document = Html::parse_document(html);
+
+    let mut questions = Vec::new();
+    for item in document.select(&selector(CONTEST_QUESTION)) {
+        let anchor = item
+            .select(&selector(CONTEST_QUESTION_ANCHOR))
+            .next()
+            .ok_or(ScrapeError::MissingElement(CONTEST_QUESTION_ANCHOR.into()))?;
+
+        // Problem slug is the last segment of its url ( eg. /contest/weekly-contest-400/problems/two-sum/ )
+        let slug = anchor
+            .value()
+            .attr("href")
+            .ok_or(ScrapeError::MissingAttribute("href"))?
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        let points = item
+            .select(&selector(POINTS_BADGE))
+            .next()
+            .ok_or(ScrapeError::MissingElement(POINTS_BADGE.into()))?
+            .text()
+            .collect::<String>();
+        let points = points
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| ScrapeError::ParseNumber(points.clone()))?;
+
+        questions.push(ContestQuestion {
+            slug,
+            name: anchor.text().collect::<String>().trim().to_string(),
+            points,
+        });
+    }
+
+    Ok(questions)
+}
+
+/**
+Contest scraping function, for a weekly or biweekly contest by its slug ( eg. weekly-contest-400 ).
+Problems of the contest are scraped as problemset problems, along with their points, and so get the
+details asked for in the options as well. Kept apart from the problemset scraper, as the contest page
+has a layout of its own.
+*/
+#[instrument(skip(driver, options))]
+pub async fn scrape_contest(
+    driver: &WebDriver,
+    slug: &str,
+    options: &ScrapeOptions,
+) -> Result<Vec<PotdInfo>, ScrapeError> {
+    step(options, &format!("Navigating to the contest {slug}")).await;
+    driver
+        .goto(format!("{}/contest/{slug}/", options.domain()))
+        .await?;
+
+    // Wait for the problem list to hydrate
+    step(options, "Waiting for the contest problems to hydrate").await;
+    let mut query = driver.query(css(CONTEST_QUESTION_LIST));
+    if let Some((timeout, poll_interval)) = options.wait_timing() {
+        query = query.wait(timeout, poll_interval);
+    }
+    let html = query.first().await?.outer_html().await?;
+
+    let questions = parse_contest_html(&html)?;
+    info!(problems = questions.len(), "found the contest problems");
+
+    let mut problems = Vec::with_capacity(questions.len());
+    for question in questions {
+        step(
+            options,
+            &format!("Scraping the contest problem {}", question.slug),
+        )
+        .await;
+        let info = driver
+            .execute_async(QUESTION_SCRIPT, vec![serde_json::json!(question.slug)])
+            .await?
+            .convert::<Option<Question>>()?
+            .ok_or_else(|| ScrapeError::UnknownProblem(question.slug.clone()))?;
+
+        let number = info
+            .question_frontend_id
+            .parse::<u32>()
+            .map_err(|_| ScrapeError::ParseNumber(info.question_frontend_id.clone()))?;
+        let acceptance = serde_json::from_str::<QuestionStats>(&info.stats)
+            .map_err(|_| ScrapeError::ParseRate(info.stats.clone()))?
+            .ac_rate;
+        let acceptance_rate = acceptance
+            .trim_end_matches('%')
+            .parse::<f32>()
+            .map_err(|_| ScrapeError::ParseRate(acceptance.clone()))?
+            / 100.0;
+
+        let mut problem = PotdInfo {
+            number,
+            name: question.name,
+            url: format!("{}/problems/{}/", options.domain(), question.slug),
+            slug: question.slug.clone(),
+            difficulty: info.difficulty.parse::<Difficulty>()?,
+            acceptance_rate,
+            is_premium: info.is_paid_only,
+            solution_url: None,
+            points: Some(question.points),
+            tags: Vec::new(),
+            companies: Vec::new(),
+            likes: None,
+            dislikes: None,
+            solution_count: None,
+            similar_questions: Vec::new(),
+            description_html: None,
+            examples: Vec::new(),
+            constraints: Vec::new(),
+            code_templates: BTreeMap::new(),
+        };
+        scrape_problem_details(driver, options, &mut problem).await?;
+        problems.push(problem);
+    }
+
+    Ok(problems)
+}
@@ -0,0 +1,107 @@
+use super::{selector, Example};
+use scraper::{ElementRef, Html, Node};
+
+// Examples are laid out in code blocks, or in example blocks in the newer problem statements
+static EXAMPLE_BLOCK: &str = "pre, div.example-block";
+
+/// Text content of the element, with superscripts written as powers ( eg. 10<sup>5</sup> -> 10^5 ).
+fn plain_text(element: ElementRef) -> String {
+    let mut text = String::new();
+    for child in element.children() {
+        match child.value() {
+            Node::Text(content) => text.push_str(content),
+            Node::Element(_) => {
+                let child = ElementRef::wrap(child).expect("to be an element node");
+                if child.value().name() == "sup" {
+                    text.push('^');
+                }
+                text.push_str(&plain_text(child));
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+/// List right after the element, if there is one.
+fn next_list(element: ElementRef) -> Option<ElementRef> {
+    element
+        .next_siblings()
+        .filter_map(ElementRef::wrap)
+        .next()
+        .filter(|sibling| matches!(sibling.value().name(), "ul" | "ol"))
+}
+
+/// List following the heading, which might be wrapped in a paragraph of its own.
+fn list_after(heading: ElementRef) -> Option<ElementRef> {
+    next_list(heading).or_else(|| next_list(ElementRef::wrap(heading.parent()?)?))
+}
+
+/**
+Parses the constraints of a problem from its description html, as the items of the list
+following the "Constraints:" heading ( eg. 1 <= nums.length <= 10^4 ).
+A description without any constraints listed yields none.
+*/
+pub fn parse_constraints_html(description_html: &str) -> Vec<String> {
+    let fragment = Html::parse_fragment(description_html);
+
+    let list = fragment
+        .select(&selector("p, strong, b"))
+        .filter(|heading| plain_text(*heading).trim() == "Constraints:")
+        .find_map(list_after);
+    let Some(list) = list else {
+        return Vec::new();
+    };
+
+    list.children()
+        .filter_map(ElementRef::wrap)
+        .filter(|item| item.value().name() == "li")
+        .map(|item| {
+            // Whitespace of the html source is collapsed, as a browser would render it
+            plain_text(item)
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .filter(|constraint| !constraint.is_empty())
+        .collect()
+}
+
+/// Example parsed from the text of its block, if it is labelled with an input and an output.
+fn parse_example(text: &str) -> Option<Example> {
+    let (_, rest) = text.split_once("Input:")?;
+    let (input, rest) = rest.split_once("Output:")?;
+    let (output, explanation) = match rest.split_once("Explanation:") {
+        Some((output, explanation)) => (output, Some(explanation.trim().to_string())),
+        None => (rest, None),
+    };
+
+    Some(Example {
+        input: input.trim().to_string(),
+        output: output.trim().to_string(),
+        explanation,
+        raw: None,
+    })
+}
+
+/**
+Parses the example test cases of a problem from its description html, out of the example blocks
+labelled with an `Input:`, an `Output:` and optionally an `Explanation:`.
+Examples which are formatted any other way are kept as their raw text.
+*/
+pub fn parse_examples_html(description_html: &str) -> Vec<Example> {
+    let fragment = Html::parse_fragment(description_html);
+
+    fragment
+        .select(&selector(EXAMPLE_BLOCK))
+        .map(|block| {
+            let text = plain_text(block);
+            parse_example(&text).unwrap_or_else(|| Example {
+                input: String::new(),
+                output: String::new(),
+                explanation: None,
+                raw: Some(text.trim().to_string()),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,120 @@
+use super::{
+    scrape::{css, scrape_problem_details},
+    PotdInfo, ScrapeError, ScrapeOptions,
+};
+use std::future::Future;
+use thirtyfour::{prelude::ElementQueryable, WebDriver, WebElement};
+
+/**
+Element query operations that the POTD scraping is done with, so that it can be run against canned
+elements ( eg. in tests ) rather than a live browser. Selectors are always css selectors.
+*/
+pub trait ScrapeElement: Sized + Send + Sync {
+    /// First element matching the selector under this element, failing when there is none.
+    fn find(&self, selector: &str) -> impl Future<Output = Result<Self, ScrapeError>> + Send;
+
+    /// Every element matching the selector under this element, without waiting for any to show up.
+    fn query(&self, selector: &str) -> impl Future<Output = Result<Vec<Self>, ScrapeError>> + Send;
+
+    /// Value of the attribute of this element, when it has one.
+    fn attr(&self, name: &str) -> impl Future<Output = Result<Option<String>, ScrapeError>> + Send;
+
+    /// Html of the content of this element.
+    fn inner_html(&self) -> impl Future<Output = Result<String, ScrapeError>> + Send;
+
+    /// Html of this element along with its content.
+    fn outer_html(&self) -> impl Future<Output = Result<String, ScrapeError>> + Send;
+
+    /// Rendered text of this element.
+    fn text(&self) -> impl Future<Output = Result<String, ScrapeError>> + Send;
+
+    /// Clicks this element, as a user would.
+    fn click(&self) -> impl Future<Output = Result<(), ScrapeError>> + Send;
+}
+
+/**
+Page operations that the POTD scraping is done with, on top of the element queries. Details of the problem
+page ( tags, votes, the description, etc. ) are left to the driver, as they take a live browser to scrape.
+*/
+pub trait ScrapeDriver: Sync {
+    type Element: ScrapeElement;
+
+    /// Navigates to the url, once the current page is done loading.
+    fn goto(&self, url: &str) -> impl Future<Output = Result<(), ScrapeError>> + Send;
+
+    /// First element matching the selector on the page, failing when there is none.
+    fn find(
+        &self,
+        selector: &str,
+    ) -> impl Future<Output = Result<Self::Element, ScrapeError>> + Send;
+
+    /// Every element matching the selector on the page, without waiting for any to show up.
+    fn query(
+        &self,
+        selector: &str,
+    ) -> impl Future<Output = Result<Vec<Self::Element>, ScrapeError>> + Send;
+
+    /// Scrapes the details of the problem page asked for in the options into the problem.
+    fn scrape_problem_details(
+        &self,
+        options: &ScrapeOptions,
+        problem: &mut PotdInfo,
+    ) -> impl Future<Output = Result<(), ScrapeError>> + Send;
+}
+
+impl ScrapeElement for WebElement {
+    async fn find(&self, selector: &str) -> Result<Self, ScrapeError> {
+        Ok(WebElement::find(self, css(selector)).await?)
+    }
+
+    async fn query(&self, selector: &str) -> Result<Vec<Self>, ScrapeError> {
+        Ok(ElementQueryable::query(self, css(selector))
+            .nowait()
+            .all()
+            .await?)
+    }
+
+    async fn attr(&self, name: &str) -> Result<Option<String>, ScrapeError> {
+        Ok(WebElement::attr(self, name).await?)
+    }
+
+    async fn inner_html(&self) -> Result<String, ScrapeError> {
+        Ok(WebElement::inner_html(self).await?)
+    }
+
+    async fn outer_html(&self) -> Result<String, ScrapeError> {
+        Ok(WebElement::outer_html(self).await?)
+    }
+
+    async fn text(&self) -> Result<String, ScrapeError> {
+        Ok(WebElement::text(self).await?)
+    }
+
+    async fn click(&self) -> Result<(), ScrapeError> {
+        Ok(WebElement::click(self).await?)
+    }
+}
+
+impl ScrapeDriver for WebDriver {
+    type Element = WebElement;
+
+    async fn goto(&self, url: &str) -> Result<(), ScrapeError> {
+        Ok(self.handle.goto(url).await?)
+    }
+
+    async fn find(&self, selector: &str) -> Result<WebElement, ScrapeError> {
+        Ok(self.handle.find(css(selector)).await?)
+    }
+
+    async fn query(&self, selector: &str) -> Result<Vec<WebElement>, ScrapeError> {
+        Ok(self.handle.find_all(css(selector)).await?)
+    }
+
+    async fn scrape_problem_details(
+        &self,
+        options: &ScrapeOptions,
+        problem: &mut PotdInfo,
+    ) -> Result<(), ScrapeError> {
+        scrape_problem_details(self, options, problem).await
+    }
+}
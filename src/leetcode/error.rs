@@ -0,0 +1,65 @@
+use std::{borrow::Cow, error::Error, fmt};
+use thirtyfour::prelude::WebDriverError;
+
+/// Errors that can occur while scraping, distinguishing layout changes from WebDriver failures.
+#[derive(Debug)]
+pub enum ScrapeError {
+    WebDriver(WebDriverError),
+    MissingElement(Cow<'static, str>),
+    MissingAttribute(&'static str),
+    UnexpectedNameFormat(String),
+    ParseNumber(String),
+    ParseRate(String),
+    UnexpectedDifficulty(String),
+    UnknownProblem(String),
+    UnexpectedUrl(String),
+    NotPotdRow,
+    TableNotReady,
+}
+
+impl fmt::Display for ScrapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WebDriver(err) => write!(f, "webdriver failure: {err}"),
+            Self::MissingElement(selector) => {
+                write!(f, "expected an element matching '{selector}'")
+            }
+            Self::MissingAttribute(attribute) => {
+                write!(f, "expected element to have a '{attribute}' attribute")
+            }
+            Self::UnexpectedNameFormat(name) => {
+                write!(
+                    f,
+                    "expected '<number>. <name>' problem title, found '{name}'"
+                )
+            }
+            Self::ParseNumber(number) => write!(f, "unexpected problem number '{number}'"),
+            Self::ParseRate(rate) => write!(f, "unexpected acceptance rate '{rate}'"),
+            Self::UnexpectedDifficulty(text) => write!(f, "unexpected problem difficulty '{text}'"),
+            Self::UnknownProblem(slug) => write!(f, "no problem found for the slug '{slug}'"),
+            Self::UnexpectedUrl(url) => {
+                write!(
+                    f,
+                    "expected a '/problems/<slug>/' problem url, found '{url}'"
+                )
+            }
+            Self::NotPotdRow => write!(f, "expected the first problem row to be the POTD"),
+            Self::TableNotReady => write!(f, "expected the problems table to have loaded"),
+        }
+    }
+}
+
+impl Error for ScrapeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::WebDriver(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<WebDriverError> for ScrapeError {
+    fn from(err: WebDriverError) -> Self {
+        Self::WebDriver(err)
+    }
+}
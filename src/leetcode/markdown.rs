@@ -0,0 +1,115 @@
+use scraper::{ElementRef, Html, Node};
+
+/**
+Converts the problem description html to Markdown, keeping the formatting that LeetCode uses
+( paragraphs, emphasis, inline code, code blocks, lists, links, images and superscripts ).
+Any other element is rendered as just its text content.
+*/
+pub fn html_to_markdown(html: &str) -> String {
+    let fragment = Html::parse_fragment(html);
+
+    let mut markdown = String::new();
+    render_children(fragment.root_element(), &mut markdown, false);
+
+    // Consecutive blocks would leave more blank lines than needed between them
+    let mut collapsed = String::with_capacity(markdown.len());
+    for line in markdown.trim().lines().map(str::trim_end) {
+        if !(line.is_empty() && collapsed.ends_with("\n\n")) {
+            collapsed.push_str(line);
+            collapsed.push('\n');
+        }
+    }
+
+    collapsed
+}
+
+fn render_children(element: ElementRef, markdown: &mut String, in_pre: bool) {
+    for child in element.children() {
+        match child.value() {
+            // Whitespace is only significant inside code blocks
+            Node::Text(text) if in_pre => markdown.push_str(text),
+            Node::Text(text) => {
+                // Runs of whitespace are collapsed to a single space, as a browser would render them
+                let mut collapsed = String::with_capacity(text.len());
+                for c in text.chars() {
+                    match c.is_whitespace() {
+                        true if !collapsed.ends_with(' ') => collapsed.push(' '),
+                        true => {}
+                        false => collapsed.push(c),
+                    }
+                }
+
+                match markdown.is_empty() || markdown.ends_with([' ', '\n']) {
+                    true => markdown.push_str(collapsed.trim_start()),
+                    false => markdown.push_str(&collapsed),
+                }
+            }
+            Node::Element(_) => {
+                let child = ElementRef::wrap(child).expect("to be an element node");
+                render_element(child, markdown, in_pre);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn render_element(element: ElementRef, markdown: &mut String, in_pre: bool) {
+    // Code blocks can't hold any formatting, and so only their text is kept
+    let inline = |markdown: &mut String, marker: &str| {
+        let marker = if in_pre { "" } else { marker };
+        markdown.push_str(marker);
+        render_children(element, markdown, in_pre);
+        markdown.push_str(marker);
+    };
+
+    match element.value().name() {
+        "p" | "div" => {
+            render_children(element, markdown, in_pre);
+            markdown.push_str("\n\n");
+        }
+        "br" => markdown.push('\n'),
+        "strong" | "b" => inline(markdown, "**"),
+        "em" | "i" => inline(markdown, "*"),
+        "code" => inline(markdown, "`"),
+        "sup" => {
+            markdown.push('^');
+            render_children(element, markdown, in_pre);
+        }
+        "pre" => {
+            markdown.push_str("\n```\n");
+            render_children(element, markdown, true);
+            if !markdown.ends_with('\n') {
+                markdown.push('\n');
+            }
+            markdown.push_str("```\n\n");
+        }
+        list @ ("ul" | "ol") => {
+            markdown.push('\n');
+            let items = element
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|item| item.value().name() == "li");
+            for (index, item) in items.enumerate() {
+                match list {
+                    "ol" => markdown.push_str(&format!("{}. ", index + 1)),
+                    _ => markdown.push_str("- "),
+                }
+                render_children(item, markdown, in_pre);
+                markdown.push('\n');
+            }
+            markdown.push('\n');
+        }
+        "a" => {
+            markdown.push('[');
+            render_children(element, markdown, in_pre);
+            let href = element.value().attr("href").unwrap_or_default();
+            markdown.push_str(&format!("]({href})"));
+        }
+        "img" => {
+            let alt = element.value().attr("alt").unwrap_or_default();
+            let src = element.value().attr("src").unwrap_or_default();
+            markdown.push_str(&format!("![{alt}]({src})"));
+        }
+        _ => render_children(element, markdown, in_pre),
+    }
+}
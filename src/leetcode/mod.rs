@@ -0,0 +1,47 @@
+/*!
+LeetCode problemset scraper, for fetching the problem of the day ( POTD ), the whole problemset or the problems of a contest.
+Scraping is done through a WebDriver session, as the problemset page is hydrated client-side.
+*/
+
+mod contest;
+mod description;
+mod driver;
+mod error;
+mod markdown;
+mod output;
+mod parse;
+mod problem;
+mod scrape;
+mod session;
+mod sqlite;
+
+pub use contest::{parse_contest_html, scrape_contest, ContestQuestion};
+pub use description::{parse_constraints_html, parse_examples_html};
+pub use driver::{ScrapeDriver, ScrapeElement};
+pub use error::ScrapeError;
+pub use markdown::html_to_markdown;
+pub use output::{
+    parse_history, problem_file_name, problems_to_csv, prune_history, slugify, ContestDocument,
+    DatedPotd, HistoryDocument, OutputFormat, PotdDocument, ProblemsDocument,
+};
+pub(crate) use parse::selector;
+pub use parse::{
+    is_potd_row_html, parse_column_layout_html, parse_problem_row_html, parse_problemset_html,
+    ColumnLayout, Problemset, RowSelectors,
+};
+pub use problem::{parse_count, problem_slug, Difficulty, Example, PotdInfo, SimilarProblem};
+pub use scrape::{
+    fetch_potd, is_potd_row, is_stale_element, parse_problem_row, scrape_all,
+    scrape_code_templates, scrape_companies, scrape_potd, scrape_similar_questions,
+    scrape_solution_count, scrape_tags, scrape_votes, ScrapeOptions,
+};
+pub use session::{add_session_cookies, SessionCookies, SESSION_COOKIE};
+pub use sqlite::upsert_problems;
+
+/**
+Default site the problems are scraped from, which `ScrapeOptions::domain` overrides ( eg. for leetcode.cn ).
+Only the links are resolved on the domain, while the selectors are kept as statics at the top of the
+parse and scrape modules, and so might need adjusting for the layout of an alternate site
+( the ones of the problem row cells can be overridden with `ScrapeOptions::selectors` ).
+*/
+pub static LEETCODE_DOMAIN: &str = "https://leetcode.com";
@@ -0,0 +1,212 @@
+use super::{Difficulty, PotdInfo};
+use chrono::NaiveDate;
+use std::{error::Error, str::FromStr};
+
+/// Serialization format of the output documents.
+#[derive(Clone, Copy)]
+pub enum OutputFormat {
+    Toml,
+    Json,
+    Yaml,
+    /// Problem rows only, as written with `problems_to_csv`
+    Csv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "toml" => Ok(Self::Toml),
+            "json" => Ok(Self::Json),
+            "yaml" => Ok(Self::Yaml),
+            "csv" => Ok(Self::Csv),
+            format => Err(format!("unsupported output format '{format}'")),
+        }
+    }
+}
+
+impl OutputFormat {
+    /// Serializes the document in this format, pretty-printed unless compact ( which YAML always is ).
+    pub fn serialize<T: serde::Serialize>(
+        self,
+        document: &T,
+        compact: bool,
+    ) -> Result<String, Box<dyn Error>> {
+        Ok(match (self, compact) {
+            (Self::Toml, false) => toml::to_string_pretty(document)?,
+            (Self::Toml, true) => toml::to_string(document)?,
+            (Self::Json, false) => serde_json::to_string_pretty(document)?,
+            (Self::Json, true) => serde_json::to_string(document)?,
+            (Self::Yaml, _) => serde_yaml::to_string(document)?,
+            // Documents are nested, which a flat CSV table can't hold
+            (Self::Csv, _) => return Err("csv output only holds problem rows".into()),
+        })
+    }
+
+    /// File extension of the documents in this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Toml => "toml",
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+            Self::Csv => "csv",
+        }
+    }
+
+    /// Deserializes a document in this format.
+    pub fn deserialize<T: serde::de::DeserializeOwned>(
+        self,
+        content: &str,
+    ) -> Result<T, Box<dyn Error>> {
+        Ok(match self {
+            Self::Toml => toml::from_str(content)?,
+            Self::Json => serde_json::from_str(content)?,
+            Self::Yaml => serde_yaml::from_str(content)?,
+            Self::Csv => return Err("csv output can't be read back".into()),
+        })
+    }
+}
+
+/// PotdInfo along with the date it was scraped on.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct DatedPotd {
+    pub date: String,
+    #[serde(flatten)]
+    pub potd: PotdInfo,
+}
+
+/// Output document structure, keeping the POTD under a `potd` key in every format.
+#[derive(serde::Serialize)]
+pub struct PotdDocument {
+    pub potd: DatedPotd,
+}
+
+/// Output document structure for the POTD history, one entry per date.
+#[derive(serde::Serialize)]
+pub struct HistoryDocument {
+    pub potd: Vec<DatedPotd>,
+}
+
+/// Stored document structure, in either the single POTD format or the history format.
+#[derive(serde::Deserialize)]
+struct StoredDocument {
+    potd: StoredPotd,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum StoredPotd {
+    History(Vec<DatedPotd>),
+    Single(Box<DatedPotd>),
+}
+
+/// Output document structure for the whole problemset, scraped on a particular date.
+#[derive(serde::Serialize)]
+pub struct ProblemsDocument {
+    pub date: String,
+    pub problems: Vec<PotdInfo>,
+}
+
+/// Output document structure for the problems of a contest, scraped on a particular date.
+#[derive(serde::Serialize)]
+pub struct ContestDocument {
+    pub contest: String,
+    pub date: String,
+    pub problems: Vec<PotdInfo>,
+}
+
+/// Parses the POTD history from the content of a history file, migrating it if it holds a single POTD.
+pub fn parse_history(
+    content: &str,
+    format: OutputFormat,
+) -> Result<Vec<DatedPotd>, Box<dyn Error>> {
+    // First run would not have any history yet
+    if content.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let document = format.deserialize::<StoredDocument>(content)?;
+    Ok(match document.potd {
+        StoredPotd::History(history) => history,
+        StoredPotd::Single(potd) => vec![*potd],
+    })
+}
+
+/**
+Prunes the POTD history entries dated before the given date, where the entry dates are parsed in the date format
+they were recorded in. Entries whose dates don't parse are kept, as they can't be told apart from recent ones.
+Returns the number of pruned entries.
+*/
+pub fn prune_history(history: &mut Vec<DatedPotd>, since: NaiveDate, date_format: &str) -> usize {
+    let entries = history.len();
+    history.retain(|entry| {
+        NaiveDate::parse_from_str(&entry.date, date_format).map_or(true, |date| date >= since)
+    });
+
+    entries - history.len()
+}
+
+/**
+Slugifies the problem name to lowercase alphanumeric words joined by dashes ( eg. Two Sum -> two-sum ).
+Every other character is dropped, and so are the characters which are invalid in Windows file names.
+*/
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.chars() {
+        match c.is_alphanumeric() {
+            true => slug.extend(c.to_lowercase()),
+            false if !slug.is_empty() && !slug.ends_with('-') => slug.push('-'),
+            false => {}
+        }
+    }
+
+    slug.trim_end_matches('-').to_string()
+}
+
+/**
+File name of the problem in a problem archive, as `{number}-{slug}.{ext}`, so that it matches the problem url.
+Problems read back from before the slug was recorded fall back to the slugified name.
+*/
+pub fn problem_file_name(problem: &PotdInfo, format: OutputFormat) -> String {
+    let slug = match problem.slug.is_empty() {
+        true => slugify(&problem.name),
+        false => problem.slug.clone(),
+    };
+    format!("{}-{slug}.{}", problem.number, format.extension())
+}
+
+/// Row of the problems CSV table, in the column order of its header.
+#[derive(serde::Serialize)]
+struct CsvRow<'a> {
+    number: u32,
+    name: &'a str,
+    url: &'a str,
+    difficulty: &'a Difficulty,
+    acceptance_rate: f32,
+    solution_url: Option<&'a str>,
+    is_premium: bool,
+}
+
+/**
+Writes the problems as a CSV table with a header row, for an import into a spreadsheet.
+Only the problem listing fields are kept, as the scraped page details don't fit in a single cell.
+*/
+pub fn problems_to_csv<'a>(
+    problems: impl IntoIterator<Item = &'a PotdInfo>,
+) -> Result<String, Box<dyn Error>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for problem in problems {
+        writer.serialize(CsvRow {
+            number: problem.number,
+            name: &problem.name,
+            url: &problem.url,
+            difficulty: &problem.difficulty,
+            acceptance_rate: (problem.acceptance_rate * 10_000.0).round() / 10_000.0,
+            solution_url: problem.solution_url.as_deref(),
+            is_premium: problem.is_premium,
+        })?;
+    }
+
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
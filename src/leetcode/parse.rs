@@ -0,0 +1,328 @@
+use super::{problem_slug, Difficulty, PotdInfo, ScrapeError};
+use scraper::{ElementRef, Html, Selector};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    error::Error,
+};
+use tracing::debug;
+
+// Problems table selectors, shared by the POTD and the whole problemset scrapers
+pub(super) static PROBLEM_ROW: &str = "div[role='row']";
+pub(super) static COLUMN_HEADER: &str = "div[role='columnheader']";
+static CELL: &str = "div[role='cell']";
+static CELL_ANCHOR: &str = "a";
+static SOLUTION_ANCHOR: &str = "a[aria-label='solution']";
+static DIFFICULTY_LABEL: &str = "span";
+pub(super) static POTD_MARKER: &str = "div[role='cell'] > a > svg";
+static PREMIUM_MARKER: &str = "svg[data-icon='lock'], [data-premium='true']";
+
+/**
+Column positions of the problems table ( 1-based, as the `:nth-child` of the row ), so that a
+reordering of the columns doesn't break the parsing. Defaults to the current LeetCode layout.
+*/
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColumnLayout {
+    pub title: usize,
+    pub solution: usize,
+    pub acceptance: usize,
+    pub difficulty: usize,
+}
+
+impl Default for ColumnLayout {
+    fn default() -> Self {
+        Self {
+            title: 2,
+            solution: 3,
+            acceptance: 4,
+            difficulty: 5,
+        }
+    }
+}
+
+impl ColumnLayout {
+    /// Selector of the problem title anchor of a row.
+    pub(super) fn title_anchor(&self, selectors: &RowSelectors) -> String {
+        format!(
+            "{CELL}:nth-child({}) {}",
+            self.title, selectors.problem_anchor
+        )
+    }
+}
+
+/**
+Selectors of the content of the problem row cells, which can be overridden from a config file when
+the LeetCode layout changes, rather than waiting on a fix. Defaults to the current LeetCode layout.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowSelectors {
+    /// Anchor of the title cell, linking to the problem.
+    pub problem_anchor: Cow<'static, str>,
+    /// Anchor of the solution cell, linking to the editorial.
+    pub solution_anchor: Cow<'static, str>,
+    /// Label of the difficulty cell.
+    pub difficulty_label: Cow<'static, str>,
+}
+
+impl Default for RowSelectors {
+    fn default() -> Self {
+        Self {
+            problem_anchor: Cow::Borrowed(CELL_ANCHOR),
+            solution_anchor: Cow::Borrowed(SOLUTION_ANCHOR),
+            difficulty_label: Cow::Borrowed(DIFFICULTY_LABEL),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SelectorOverrides {
+    problem_anchor: Option<String>,
+    solution_anchor: Option<String>,
+    difficulty_label: Option<String>,
+}
+
+impl RowSelectors {
+    /**
+    Parses the selector overrides of a TOML config file, where the selectors left out keep their defaults.
+    Overrides are validated upfront, rather than on the first row parsed with them.
+    */
+    pub fn from_toml(config: &str) -> Result<Self, Box<dyn Error>> {
+        let overrides = toml::from_str::<SelectorOverrides>(config)?;
+
+        let resolve = |name: &str, selector: Option<String>, default| match selector {
+            Some(selector) => {
+                if let Err(err) = Selector::parse(&selector) {
+                    return Err(format!("invalid {name} selector '{selector}' ( {err} )"));
+                }
+                Ok(Cow::Owned(selector))
+            }
+            None => Ok(default),
+        };
+        let defaults = Self::default();
+        Ok(Self {
+            problem_anchor: resolve(
+                "problem_anchor",
+                overrides.problem_anchor,
+                defaults.problem_anchor,
+            )?,
+            solution_anchor: resolve(
+                "solution_anchor",
+                overrides.solution_anchor,
+                defaults.solution_anchor,
+            )?,
+            difficulty_label: resolve(
+                "difficulty_label",
+                overrides.difficulty_label,
+                defaults.difficulty_label,
+            )?,
+        })
+    }
+}
+
+/// Parsed problems table, with the POTD pinned on top kept apart from the listed problems.
+pub struct Problemset {
+    pub potd: Option<PotdInfo>,
+    pub problems: Vec<PotdInfo>,
+}
+
+// Selector queries are logged, so that a broken scrape can be pinned to the selector that failed
+pub(crate) fn selector(css: &str) -> Selector {
+    debug!(selector = css, "querying css selector");
+    Selector::parse(css).expect("to be a valid selector")
+}
+
+fn find<'a>(element: ElementRef<'a>, css: &str) -> Result<ElementRef<'a>, ScrapeError> {
+    element
+        .select(&selector(css))
+        .next()
+        .ok_or_else(|| ScrapeError::MissingElement(css.to_string().into()))
+}
+
+fn text(element: ElementRef) -> String {
+    element.text().collect::<String>().trim().to_string()
+}
+
+/**
+Detects the column layout from the header cells of the problems table, matched on their text
+( Title, Solution, Acceptance and Difficulty ). Falls back to the default layout when any
+of the headers can't be read.
+*/
+pub fn parse_column_layout_html(table: ElementRef) -> ColumnLayout {
+    let Some(header_row) = table
+        .select(&selector(PROBLEM_ROW))
+        .find(|row| row.select(&selector(COLUMN_HEADER)).next().is_some())
+    else {
+        return ColumnLayout::default();
+    };
+
+    let columns = header_row
+        .select(&selector(COLUMN_HEADER))
+        .enumerate()
+        .map(|(index, header)| (text(header).to_lowercase(), index + 1))
+        .collect::<HashMap<_, _>>();
+
+    match (
+        columns.get("title"),
+        columns.get("solution"),
+        columns.get("acceptance"),
+        columns.get("difficulty"),
+    ) {
+        (Some(&title), Some(&solution), Some(&acceptance), Some(&difficulty)) => ColumnLayout {
+            title,
+            solution,
+            acceptance,
+            difficulty,
+        },
+        _ => ColumnLayout::default(),
+    }
+}
+
+/// Absolute url of the href on the domain, which is kept as is when it's already absolute.
+pub(super) fn absolute_url(domain: &str, href: &str) -> String {
+    match href.starts_with("http://") || href.starts_with("https://") {
+        true => href.to_string(),
+        false => format!("{}{href}", domain.trim_end_matches('/')),
+    }
+}
+
+/// Cell of the row at the 1-based column position.
+fn cell(row: ElementRef, column: usize) -> Result<ElementRef, ScrapeError> {
+    row.children()
+        .filter_map(ElementRef::wrap)
+        .filter(|child| child.value().attr("role") == Some("cell"))
+        .nth(column - 1)
+        .ok_or(ScrapeError::MissingElement(CELL.into()))
+}
+
+/// Checks if the problem row is the POTD, which would have a unique SVG element.
+pub fn is_potd_row_html(row: ElementRef) -> bool {
+    row.select(&selector(POTD_MARKER)).next().is_some()
+}
+
+/**
+Problem row parsing function, on the html of the row. Returns PotdInfo if it was successful in parsing the row.
+Cells are looked up at the positions of the column layout, and their content with the row selectors.
+Links are resolved on the domain.
+*/
+pub fn parse_problem_row_html(
+    row: ElementRef,
+    layout: &ColumnLayout,
+    selectors: &RowSelectors,
+    domain: &str,
+) -> Result<PotdInfo, ScrapeError> {
+    let problem_anchor = find(cell(row, layout.title)?, &selectors.problem_anchor)?;
+
+    // Problem anchor's content would be Title ( Problem Number + Name )
+    let name = text(problem_anchor);
+    let (number, name) = name
+        .split_once(". ")
+        .ok_or_else(|| ScrapeError::UnexpectedNameFormat(name.clone()))?;
+    let number = number
+        .parse::<u32>()
+        .map_err(|_| ScrapeError::ParseNumber(number.to_string()))?;
+
+    let url = absolute_url(
+        domain,
+        problem_anchor
+            .value()
+            .attr("href")
+            .ok_or(ScrapeError::MissingAttribute("href"))?,
+    );
+    let slug = problem_slug(&url)
+        .ok_or_else(|| ScrapeError::UnexpectedUrl(url.clone()))?
+        .to_string();
+
+    // Acceptance cell holds a percentage ( eg. 52.3% )
+    let acceptance = text(cell(row, layout.acceptance)?);
+    let acceptance_rate = acceptance
+        .trim_end_matches('%')
+        .parse::<f32>()
+        .map_err(|_| ScrapeError::ParseRate(acceptance.clone()))?
+        / 100.0;
+
+    // Difficulty cell holds a color-coded span with the difficulty label
+    let difficulty = text(find(
+        cell(row, layout.difficulty)?,
+        &selectors.difficulty_label,
+    )?)
+    .parse::<Difficulty>()?;
+
+    // Premium ( locked ) problems are marked with a lock icon or a premium badge
+    let is_premium = row.select(&selector(PREMIUM_MARKER)).next().is_some();
+
+    // Solutions of premium problems are locked as well, and so are not looked up
+    let solution_url = match is_premium {
+        true => None,
+        // A solution anchor might not be present ( no solution exists )
+        false => match cell(row, layout.solution)?
+            .select(&selector(&selectors.solution_anchor))
+            .next()
+        {
+            Some(solution_anchor) => Some(absolute_url(
+                domain,
+                solution_anchor
+                    .value()
+                    .attr("href")
+                    .ok_or(ScrapeError::MissingAttribute("href"))?,
+            )),
+            None => None,
+        },
+    };
+
+    Ok(PotdInfo {
+        number,
+        name: name.to_string(),
+        url,
+        slug,
+        difficulty,
+        acceptance_rate,
+        is_premium,
+        solution_url,
+        points: None,
+        tags: Vec::new(),
+        companies: Vec::new(),
+        likes: None,
+        dislikes: None,
+        solution_count: None,
+        similar_questions: Vec::new(),
+        description_html: None,
+        examples: Vec::new(),
+        constraints: Vec::new(),
+        code_templates: BTreeMap::new(),
+    })
+}
+
+/**
+Problemset parsing function, on the html of a page with the problems table ( or just a part of it ).
+Every row is parsed at the column layout detected from the header, with the POTD row being
+returned apart from the rest. Links are resolved on the domain ( eg. `LEETCODE_DOMAIN` ).
+*/
+pub fn parse_problemset_html(
+    html: &str,
+    selectors: &RowSelectors,
+    domain: &str,
+) -> Result<Problemset, ScrapeError> {
+    let document = Html::parse_document(html);
+    let layout = parse_column_layout_html(document.root_element());
+    let title_anchor = selector(&layout.title_anchor(selectors));
+
+    let mut problemset = Problemset {
+        potd: None,
+        problems: Vec::new(),
+    };
+    for row in document.select(&selector(PROBLEM_ROW)) {
+        // Header row doesn't link to any problem
+        if row.select(&title_anchor).next().is_none() {
+            continue;
+        }
+
+        let problem = parse_problem_row_html(row, &layout, selectors, domain)?;
+        match is_potd_row_html(row) {
+            true => problemset.potd = Some(problem),
+            false => problemset.problems.push(problem),
+        }
+    }
+
+    Ok(problemset)
+}
@@ -0,0 +1,128 @@
+use super::ScrapeError;
+use std::{collections::BTreeMap, str::FromStr};
+
+/// Problem difficulty, as labelled in the problems table.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl FromStr for Difficulty {
+    type Err = ScrapeError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim() {
+            "Easy" => Ok(Self::Easy),
+            "Medium" => Ok(Self::Medium),
+            "Hard" => Ok(Self::Hard),
+            text => Err(ScrapeError::UnexpectedDifficulty(text.to_string())),
+        }
+    }
+}
+
+/// Problem information scraped from a row of the problems table.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct PotdInfo {
+    pub number: u32,
+    pub name: String,
+    pub url: String,
+    // Slug of the problem url ( eg. two-sum ), which is a stable identifier unlike the name
+    #[serde(default)]
+    pub slug: String,
+    pub difficulty: Difficulty,
+    #[serde(serialize_with = "serialize_rate")]
+    pub acceptance_rate: f32,
+    pub is_premium: bool,
+    pub solution_url: Option<String>,
+    // Points the problem is worth in a contest, which problemset problems don't have
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    // Companies which asked the problem in interviews, which are only shown to premium accounts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub companies: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub likes: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dislikes: Option<u32>,
+    // Community solutions shared on the Solutions tab, apart from the editorial of `solution_url`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub solution_count: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub similar_questions: Vec<SimilarProblem>,
+    // Problem statement, converted to Markdown when plain text is asked for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description_html: Option<String>,
+    // Example test cases of the problem statement
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<Example>,
+    // Constraints listed at the end of the problem statement ( eg. 1 <= n <= 10^5 )
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub constraints: Vec<String>,
+    // Starter code of the problem, keyed by the language name ( eg. C++ or Python3 ), sorted so that the output is stable
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub code_templates: BTreeMap<String, String>,
+}
+
+/**
+Slug of the problem, as the path segment following `/problems/` in its url ( eg. two-sum ).
+Urls can be relative or absolute, and can go on past the slug ( eg. /problems/two-sum/description/?envType=daily ).
+*/
+pub fn problem_slug(url: &str) -> Option<&str> {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    let mut segments = path.split('/');
+    segments.find(|segment| *segment == "problems")?;
+    segments.next().filter(|slug| !slug.is_empty())
+}
+
+/// Parses an abbreviated count, as shown on the vote buttons ( eg. 987, 12.3K or 1.2M ).
+pub fn parse_count(text: &str) -> Result<u32, ScrapeError> {
+    let text = text.trim().replace(',', "");
+    let (number, scale) = match text.chars().last() {
+        Some('K' | 'k') => (&text[..text.len() - 1], 1e3),
+        Some('M' | 'm') => (&text[..text.len() - 1], 1e6),
+        Some('B' | 'b') => (&text[..text.len() - 1], 1e9),
+        _ => (text.as_str(), 1.0),
+    };
+
+    number
+        .parse::<f64>()
+        .ok()
+        .map(|number| (number * scale).round())
+        // Counts out of the u32 range would otherwise saturate silently on the cast
+        .filter(|count| count.is_finite() && (0.0..=u32::MAX as f64).contains(count))
+        .map(|count| count as u32)
+        .ok_or(ScrapeError::ParseNumber(text))
+}
+
+/// Serializes a rate rounded to 4 decimal places, avoiding the f32 -> f64 widening noise.
+fn serialize_rate<S: serde::Serializer>(rate: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64((*rate as f64 * 10_000.0).round() / 10_000.0)
+}
+
+/// Related problem listed in the "Similar Questions" panel of a problem page.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct SimilarProblem {
+    pub name: String,
+    pub url: String,
+    pub difficulty: Difficulty,
+}
+
+/**
+Example test case listed in the problem statement.
+Examples which are not laid out as an input and an output are kept as their raw text instead.
+*/
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct Example {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub input: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub output: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<String>,
+}
@@ -0,0 +1,791 @@
+use super::{
+    html_to_markdown,
+    parse::{
+        absolute_url, parse_column_layout_html, parse_problem_row_html, POTD_MARKER, PROBLEM_ROW,
+    },
+    parse_constraints_html, parse_count, parse_examples_html, selector, ColumnLayout, Difficulty,
+    PotdInfo, RowSelectors, ScrapeElement, ScrapeError, SimilarProblem, LEETCODE_DOMAIN,
+};
+use fantoccini::error::CmdError;
+use scraper::Html;
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use thirtyfour::{
+    extensions::query::ElementQuery,
+    prelude::{ElementQueryable, WebDriverError, WebDriverResult},
+    By, WebDriver, WebElement,
+};
+use tracing::{debug, debug_span, info, instrument, warn, Instrument};
+
+// Hydration waits time out and are polled at the same timeout and interval as the thirtyfour defaults
+static WAIT_TIMEOUT: Duration = Duration::from_secs(20);
+static WAIT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// Operations on an element are attempted this many times, when it goes stale as the page re-renders
+static STALE_ELEMENT_ATTEMPTS: u32 = 3;
+
+static DESCRIPTION_CONTENT: &str = "div[data-track-load='description_content']";
+
+// Company tags are listed behind a "Companies" toggle, which is only there for premium accounts
+static COMPANIES_TOGGLE: &str = "//*[normalize-space(text())='Companies']";
+static COMPANY_ANCHOR: &str = "a[href^='/company/']";
+
+// Vote buttons hold the count next to their thumbs icon
+static LIKE_BUTTON: &str = "//button[.//*[@data-icon='thumbs-up']]";
+static DISLIKE_BUTTON: &str = "//button[.//*[@data-icon='thumbs-down']]";
+
+// Solutions tab of the problem page shows the community solutions count next to its label ( eg. Solutions (1.2K) )
+static SOLUTIONS_TAB: &str = "div#solutions_tab";
+
+// Similar questions are listed inside an accordion panel, which is collapsed by default
+static SIMILAR_TOGGLE: &str = "//*[normalize-space(text())='Similar Questions']";
+static SIMILAR_ANCHOR: &str = "/ancestor::div[.//a[starts-with(@href, '/problems/')]][1]\
+    //a[starts-with(@href, '/problems/')]";
+static SIMILAR_DIFFICULTY: &str = "./ancestor::div[.//*[normalize-space(text())='Easy' \
+    or normalize-space(text())='Medium' or normalize-space(text())='Hard']][1]\
+    //*[normalize-space(text())='Easy' or normalize-space(text())='Medium' \
+    or normalize-space(text())='Hard']";
+
+// Code editor only ever holds the template of the selected language, so the templates of every
+// language are asked of the same GraphQL endpoint that the problem page loads them from
+static CODE_SNIPPETS_SCRIPT: &str = r#"
+    const [slug, done] = arguments;
+    fetch("/graphql", {
+        method: "POST",
+        headers: { "Content-Type": "application/json" },
+        body: JSON.stringify({
+            query: "query($slug: String!) { question(titleSlug: $slug) { codeSnippets { lang langSlug code } } }",
+            variables: { slug },
+        }),
+    })
+        .then((response) => response.json())
+        .then((body) => done(body?.data?.question?.codeSnippets ?? []))
+        .catch(() => done([]));
+"#;
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CodeSnippet {
+    lang: String,
+    lang_slug: String,
+    code: String,
+}
+
+/// Options controlling what gets scraped, and how the scraping can be observed.
+#[derive(Default)]
+pub struct ScrapeOptions {
+    /// Scraping steps are logged and paused on, so that they can be observed in the browser.
+    pub headful: bool,
+    /// Topic tags require an extra visit to the problem page, and so are only scraped on request.
+    pub with_tags: bool,
+    /// Problem statement requires an extra visit to the problem page as well.
+    pub with_description: bool,
+    /// Constraints are parsed out of the problem statement, which requires the extra visit as well.
+    pub with_constraints: bool,
+    /// Example test cases are parsed out of the problem statement as well.
+    pub with_examples: bool,
+    /// Company tags are read from the problem page as well, though only when authenticated.
+    pub with_companies: bool,
+    /// Like and dislike counts are read from the problem page as well.
+    pub with_votes: bool,
+    /// Community solutions count is read from the problem page as well.
+    pub with_solution_count: bool,
+    /// Similar questions are listed on the problem page as well.
+    pub with_similar: bool,
+    /// Starter code templates are read from the problem page as well.
+    pub with_templates: bool,
+    /// Site the problems are scraped from, defaulting to `LEETCODE_DOMAIN`.
+    pub domain: Option<String>,
+    /// Selectors of the problem row cells, overriding the built-in ones when the layout changes.
+    pub selectors: RowSelectors,
+    /// Only the starter code of this language is kept, matched on its name or slug ( eg. Python3 or python3 ).
+    pub template_lang: Option<String>,
+    /// Problem statement is converted from html to Markdown.
+    pub plain_text: bool,
+    /// Upper bound on the pages scraped of the whole problemset.
+    pub max_pages: Option<u32>,
+    /// Timeout of every hydration wait, instead of the thirtyfour default ( 20 seconds ).
+    pub timeout: Option<Duration>,
+    /// Polling interval of every hydration wait, instead of the thirtyfour default ( 500 milliseconds ).
+    pub poll_interval: Option<Duration>,
+    /// Session is signed in with a premium account, and so the premium problems are scraped as well.
+    pub authenticated: bool,
+    /// Once set, the whole problemset scraping stops paginating and returns the problems scraped so far.
+    pub interrupted: Arc<AtomicBool>,
+}
+
+impl ScrapeOptions {
+    /// Site the problems are scraped from, without a trailing slash.
+    pub fn domain(&self) -> &str {
+        self.domain
+            .as_deref()
+            .unwrap_or(LEETCODE_DOMAIN)
+            .trim_end_matches('/')
+    }
+
+    /// Timeout and polling interval of the hydration waits, when either of them is configured.
+    pub(super) fn wait_timing(&self) -> Option<(Duration, Duration)> {
+        match (self.timeout, self.poll_interval) {
+            (None, None) => None,
+            (timeout, poll_interval) => Some((
+                timeout.unwrap_or(WAIT_TIMEOUT),
+                poll_interval.unwrap_or(WAIT_POLL_INTERVAL),
+            )),
+        }
+    }
+}
+
+/// Logs a scraping step and pauses for a while when headful, so that it can be observed in the browser.
+pub(super) async fn step(options: &ScrapeOptions, description: &str) {
+    info!("{description}");
+    if options.headful {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+/// Query polled at the hydration timeout and interval of the options, rather than at the thirtyfour defaults.
+fn wait_options(query: ElementQuery, options: &ScrapeOptions) -> ElementQuery {
+    let (timeout, poll_interval) = options
+        .wait_timing()
+        .unwrap_or((WAIT_TIMEOUT, WAIT_POLL_INTERVAL));
+    query.wait(timeout, poll_interval)
+}
+
+/// Css query of the browser, logged like the html selector queries.
+pub(super) fn css(selector: &str) -> By {
+    debug!(selector, "querying css selector");
+    By::Css(selector)
+}
+
+/// Xpath query of the browser, logged like the html selector queries.
+fn xpath(selector: &str) -> By {
+    debug!(selector, "querying xpath selector");
+    By::XPath(selector)
+}
+
+/// Checks if the WebDriver error is of a stale element, which was re-rendered since it was found.
+pub fn is_stale_element(err: &WebDriverError) -> bool {
+    matches!(
+        err,
+        WebDriverError::CmdError(CmdError::Standard(err)) if err.error() == "stale element reference"
+    )
+}
+
+/// Checks if the scrape error is of an element re-rendered since it was found, which is worth retrying.
+fn is_rerendered(err: &ScrapeError) -> bool {
+    match err {
+        ScrapeError::WebDriver(err) => is_stale_element(err),
+        ScrapeError::NotPotdRow => true,
+        _ => false,
+    }
+}
+
+/**
+Retries the operation whenever an element it works on goes stale, as the page re-renders while hydrating.
+The operation has to find its elements afresh from their parent, so that a retry doesn't reuse the stale ones.
+Rows re-rendered in another order ( the POTD row not being the first one ) are retried alike.
+*/
+async fn retry_stale<T, F, Fut>(operation: F) -> Result<T, ScrapeError>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, ScrapeError>>,
+{
+    let mut attempt = 1;
+    loop {
+        match operation().await {
+            Err(err) if attempt < STALE_ELEMENT_ATTEMPTS && is_rerendered(&err) => {
+                debug!(attempt, "element went stale, finding it again");
+                attempt += 1;
+            }
+            res => return res,
+        }
+    }
+}
+
+/**
+Polls the condition until it holds, at the hydration timeout and polling interval of the options.
+Times out with the same error as a WebDriver wait, so that either kind of wait fails alike.
+*/
+async fn poll_until<F, Fut>(
+    options: &ScrapeOptions,
+    description: &str,
+    condition: F,
+) -> Result<(), ScrapeError>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<bool, ScrapeError>>,
+{
+    let (timeout, poll_interval) = options
+        .wait_timing()
+        .unwrap_or((WAIT_TIMEOUT, WAIT_POLL_INTERVAL));
+
+    let started = Instant::now();
+    loop {
+        if condition().await? {
+            return Ok(());
+        }
+        if started.elapsed() >= timeout {
+            return Err(WebDriverError::Timeout(format!("waiting for the {description}")).into());
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// Checks if the problem row is the POTD, which would have a unique SVG element.
+pub async fn is_potd_row<E: ScrapeElement>(row: &E) -> Result<bool, ScrapeError> {
+    Ok(!row.query(POTD_MARKER).await?.is_empty())
+}
+
+/**
+Problem row parsing function. Returns PotdInfo if it was successful in parsing the row.
+The row's html is parsed as is, so that the browser and the html parsers never drift apart.
+*/
+#[instrument(skip_all)]
+pub async fn parse_problem_row<E: ScrapeElement>(
+    row: &E,
+    layout: &ColumnLayout,
+    selectors: &RowSelectors,
+    domain: &str,
+) -> Result<PotdInfo, ScrapeError> {
+    let html = row.outer_html().await?;
+    let fragment = Html::parse_fragment(&html);
+
+    let row = fragment
+        .select(&selector(PROBLEM_ROW))
+        .next()
+        .ok_or(ScrapeError::MissingElement(PROBLEM_ROW.into()))?;
+    parse_problem_row_html(row, layout, selectors, domain)
+}
+
+/**
+Problem page navigation function. Returns the problem description container once it has hydrated.
+Descriptions of premium problems are behind a paywall, and so are only looked up when authenticated.
+*/
+#[instrument(skip_all, fields(url = problem.url))]
+async fn open_problem_page(
+    driver: &WebDriver,
+    options: &ScrapeOptions,
+    problem: &PotdInfo,
+) -> WebDriverResult<Option<WebElement>> {
+    step(
+        options,
+        &format!("Navigating to problem page {}", problem.url),
+    )
+    .await;
+    driver.goto(&problem.url).await?;
+
+    if problem.is_premium && !options.authenticated {
+        return Ok(None);
+    }
+
+    // Wait for the problem description to hydrate
+    let mut query = driver.query(css(DESCRIPTION_CONTENT));
+    if let Some((timeout, poll_interval)) = options.wait_timing() {
+        query = query.wait(timeout, poll_interval);
+    }
+    let description = query.first().await?;
+    Ok(Some(description))
+}
+
+/// Topic tags scraping function. Collects all the tag names from the currently open problem page.
+#[instrument(skip_all)]
+pub async fn scrape_tags(
+    driver: &WebDriver,
+    options: &ScrapeOptions,
+) -> WebDriverResult<Vec<String>> {
+    // Tags might be hidden behind a "show topics" toggle which has to be clicked first
+    let tags_visible = driver
+        .query(css("a[href^='/tag/']"))
+        .and_displayed()
+        .nowait()
+        .exists()
+        .await?;
+    if !tags_visible {
+        step(options, "Expanding the hidden topic tags").await;
+        let toggle = driver
+            .query(xpath(
+                "//*[normalize-space(text())='Topics' or normalize-space(text())='Show Topics']",
+            ))
+            .nowait()
+            .first_opt()
+            .await?;
+
+        // A problem might not have any tags at all, in which case there is no toggle either
+        let Some(toggle) = toggle else {
+            return Ok(Vec::new());
+        };
+        toggle.scroll_into_view().await?;
+        toggle.click().await?;
+    }
+
+    // Tags revealed by the toggle are waited on, while the visible ones are already there
+    let tag_anchors = driver.query(css("a[href^='/tag/']")).and_displayed();
+    let tag_anchors = match tags_visible {
+        true => tag_anchors.nowait(),
+        false => wait_options(tag_anchors, options),
+    }
+    .all()
+    .await?;
+
+    let mut tags = Vec::with_capacity(tag_anchors.len());
+    for tag_anchor in tag_anchors {
+        tags.push(tag_anchor.text().await?.trim().to_string());
+    }
+
+    Ok(tags)
+}
+
+/**
+Company tags scraping function. Expands the "Companies" panel of the currently open problem page and
+collects every company name. The panel is only shown to premium accounts, and so its absence isn't an error.
+*/
+#[instrument(skip_all)]
+pub async fn scrape_companies(
+    driver: &WebDriver,
+    options: &ScrapeOptions,
+) -> WebDriverResult<Vec<String>> {
+    let toggle = driver
+        .query(xpath(COMPANIES_TOGGLE))
+        .nowait()
+        .first_opt()
+        .await?;
+    let Some(toggle) = toggle else {
+        return Ok(Vec::new());
+    };
+
+    step(options, "Expanding the company tags panel").await;
+    toggle.scroll_into_view().await?;
+    toggle.click().await?;
+
+    // Panel is only waited on once expanded, and for no longer than the hydration waits
+    let company_anchors = wait_options(driver.query(css(COMPANY_ANCHOR)).and_displayed(), options)
+        .all()
+        .await?;
+
+    let mut companies = Vec::with_capacity(company_anchors.len());
+    for company_anchor in company_anchors {
+        let company = company_anchor.text().await?.trim().to_string();
+        if !company.is_empty() && !companies.contains(&company) {
+            companies.push(company);
+        }
+    }
+
+    Ok(companies)
+}
+
+/// Vote scraping function. Returns the like and dislike counts from the currently open problem page.
+#[instrument(skip_all)]
+pub async fn scrape_votes(
+    driver: &WebDriver,
+    options: &ScrapeOptions,
+) -> Result<(u32, u32), ScrapeError> {
+    step(options, "Scraping the problem votes").await;
+    let likes = driver.find(xpath(LIKE_BUTTON)).await?.text().await?;
+    let dislikes = driver.find(xpath(DISLIKE_BUTTON)).await?.text().await?;
+
+    Ok((parse_count(&likes)?, parse_count(&dislikes)?))
+}
+
+/// Solutions count scraping function. Returns the community solutions count from the currently open problem page.
+#[instrument(skip_all)]
+pub async fn scrape_solution_count(
+    driver: &WebDriver,
+    options: &ScrapeOptions,
+) -> Result<u32, ScrapeError> {
+    step(options, "Scraping the community solutions count").await;
+    let label = driver.find(css(SOLUTIONS_TAB)).await?.text().await?;
+
+    // Count is the only part of the label which parses, wherever it is placed
+    label
+        .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+        .find_map(|part| parse_count(part).ok())
+        .ok_or(ScrapeError::ParseNumber(label))
+}
+
+/**
+Similar questions scraping function. Expands the "Similar Questions" panel of the currently open
+problem page and collects every linked problem. A problem might not have any similar questions.
+*/
+#[instrument(skip_all)]
+pub async fn scrape_similar_questions(
+    driver: &WebDriver,
+    options: &ScrapeOptions,
+) -> Result<Vec<SimilarProblem>, ScrapeError> {
+    let toggle = driver
+        .query(xpath(SIMILAR_TOGGLE))
+        .nowait()
+        .first_opt()
+        .await?;
+    let Some(toggle) = toggle else {
+        return Ok(Vec::new());
+    };
+
+    step(options, "Expanding the similar questions panel").await;
+    toggle.scroll_into_view().await?;
+    toggle.click().await?;
+
+    // Revealed anchors are looked up from the panel which holds the toggle
+    let anchors = wait_options(
+        driver
+            .query(xpath(&format!("{SIMILAR_TOGGLE}{SIMILAR_ANCHOR}")))
+            .and_displayed(),
+        options,
+    )
+    .all()
+    .await?;
+
+    let mut similar_questions = Vec::with_capacity(anchors.len());
+    for anchor in anchors {
+        let href = anchor
+            .attr("href")
+            .await?
+            .ok_or(ScrapeError::MissingAttribute("href"))?;
+        let difficulty = anchor
+            .find(xpath(SIMILAR_DIFFICULTY))
+            .await?
+            .text()
+            .await?
+            .parse::<Difficulty>()?;
+
+        similar_questions.push(SimilarProblem {
+            name: anchor.text().await?.trim().to_string(),
+            url: absolute_url(options.domain(), &href),
+            difficulty,
+        });
+    }
+
+    Ok(similar_questions)
+}
+
+/**
+Code templates scraping function. Returns the starter code of the currently open problem page,
+keyed by the language name. Templates of premium problems are hidden, and so are only looked up when authenticated.
+*/
+#[instrument(skip_all, fields(url = problem.url))]
+pub async fn scrape_code_templates(
+    driver: &WebDriver,
+    options: &ScrapeOptions,
+    problem: &PotdInfo,
+) -> Result<BTreeMap<String, String>, ScrapeError> {
+    if problem.is_premium && !options.authenticated {
+        info!("skipping the code templates of a premium problem");
+        return Ok(BTreeMap::new());
+    }
+
+    step(options, "Scraping the code templates").await;
+    let snippets = driver
+        .execute_async(CODE_SNIPPETS_SCRIPT, vec![serde_json::json!(problem.slug)])
+        .await?
+        .convert::<Vec<CodeSnippet>>()?;
+
+    Ok(snippets
+        .into_iter()
+        .filter(|snippet| match &options.template_lang {
+            Some(lang) => {
+                snippet.lang.eq_ignore_ascii_case(lang)
+                    || snippet.lang_slug.eq_ignore_ascii_case(lang)
+            }
+            None => true,
+        })
+        .map(|snippet| (snippet.lang, snippet.code))
+        .collect())
+}
+
+/**
+Problemset navigation function. Returns the problems table rowgroup once it has hydrated,
+along with the column layout detected from the table header.
+*/
+#[instrument(skip_all)]
+async fn open_problemset<D: super::ScrapeDriver>(
+    driver: &D,
+    options: &ScrapeOptions,
+) -> Result<(D::Element, ColumnLayout), ScrapeError> {
+    step(options, "Navigating to the problemset").await;
+    driver
+        .goto(&format!("{}/problemset/all/", options.domain()))
+        .await?;
+
+    // Wait for body to hydrate loading screen
+    step(options, "Waiting for the page body to hydrate").await;
+    let body = &driver.find("body").await?;
+    poll_until(options, "page body to hydrate", move || async move {
+        Ok(!body.attr("class").await?.unwrap_or_default().is_empty())
+    })
+    .instrument(debug_span!("hydration", element = "body"))
+    .await?;
+
+    step(options, "Waiting for the problems table to load").await;
+    let table = driver
+        .find("div:has(div[role='table']).pointer-events-none")
+        .await;
+
+    let table = match table {
+        // Wait till Table is active and problems are loaded
+        Ok(table) => {
+            let active_table = &table;
+            poll_until(options, "problems table to load", move || async move {
+                let classes = active_table.attr("class").await?.unwrap_or_default();
+                Ok(!classes.contains("pointer-events-none"))
+            })
+            .instrument(debug_span!("hydration", element = "table"))
+            .await?;
+
+            table
+        }
+        // Unlikely case when Table has been loaded before query
+        Err(_) => driver.find("div:has(div[role='table'])").await?,
+    };
+
+    if table
+        .attr("class")
+        .await?
+        .unwrap_or_default()
+        .contains("pointer-events-none")
+    {
+        return Err(ScrapeError::TableNotReady);
+    }
+
+    // Columns might be reordered, and so their positions are read off the header
+    let layout =
+        parse_column_layout_html(Html::parse_fragment(&table.outer_html().await?).root_element());
+    debug!(?layout, "detected column layout");
+
+    Ok((table.find("div[role='rowgroup']").await?, layout))
+}
+
+/**
+POTD scraping function. Returns PotdInfo if it was successful in scraping.
+Generic over the driver, so that the scraping can be run against canned elements as well as a WebDriver.
+*/
+#[instrument(skip_all)]
+pub async fn scrape_potd<D: super::ScrapeDriver>(
+    driver: &D,
+    options: &ScrapeOptions,
+) -> Result<PotdInfo, ScrapeError> {
+    let (table, layout) = open_problemset(driver, options).await?;
+    let (table, layout) = (&table, &layout);
+
+    // Wait for Table to load POTD
+    step(options, "Waiting for the problems table to load the POTD").await;
+    // Rows going stale are re-rendered ones, which are checked again on the next poll
+    poll_until(
+        options,
+        "problems table to load the POTD",
+        move || async move {
+            let first_problem = table.find(PROBLEM_ROW).await?;
+            match is_potd_row(&first_problem).await {
+                Err(ScrapeError::WebDriver(err)) if is_stale_element(&err) => Ok(false),
+                res => res,
+            }
+        },
+    )
+    .instrument(debug_span!("hydration", element = "rowgroup"))
+    .await?;
+
+    // POTD row is found afresh from the table on every attempt, in case it goes stale
+    step(options, "Scraping the POTD row").await;
+    let mut potd_info = retry_stale(move || async move {
+        let first_problem = table.find(PROBLEM_ROW).await?;
+        if !is_potd_row(&first_problem).await? {
+            return Err(ScrapeError::NotPotdRow);
+        }
+
+        parse_problem_row(&first_problem, layout, &options.selectors, options.domain()).await
+    })
+    .await?;
+
+    driver
+        .scrape_problem_details(options, &mut potd_info)
+        .await?;
+
+    Ok(potd_info)
+}
+
+/**
+POTD fetching function, scraping only the problems table row of the POTD with the default options.
+Nothing is written anywhere, and so serializing and storing the POTD is left to the caller
+( `scrape_potd` takes the options, for the details of the problem page ).
+*/
+pub async fn fetch_potd(driver: &WebDriver) -> Result<PotdInfo, ScrapeError> {
+    scrape_potd(driver, &ScrapeOptions::default()).await
+}
+
+/**
+Problem page scraping function, for the details asked for in the options ( tags, votes, the description, etc. ).
+Visits the problem page only when any of the details are asked for.
+*/
+pub(super) async fn scrape_problem_details(
+    driver: &WebDriver,
+    options: &ScrapeOptions,
+    problem: &mut PotdInfo,
+) -> Result<(), ScrapeError> {
+    let with_companies = options.with_companies && options.authenticated;
+    let visits_page = options.with_tags
+        || with_companies
+        || options.with_description
+        || options.with_constraints
+        || options.with_examples
+        || options.with_votes
+        || options.with_solution_count
+        || options.with_similar
+        || options.with_templates;
+    if !visits_page {
+        return Ok(());
+    }
+
+    let description = open_problem_page(driver, options, problem).await?;
+
+    if options.with_tags {
+        problem.tags = scrape_tags(driver, options).await?;
+    }
+
+    // Company tags are only shown to premium accounts, and so are skipped when not signed in
+    if with_companies {
+        problem.companies = scrape_companies(driver, options).await?;
+    }
+
+    // Votes of premium problems are behind the paywall along with the description
+    if options.with_votes && description.is_some() {
+        let (likes, dislikes) = scrape_votes(driver, options).await?;
+        problem.likes = Some(likes);
+        problem.dislikes = Some(dislikes);
+    }
+
+    // Solutions of premium problems are behind the paywall along with the description
+    if options.with_solution_count && description.is_some() {
+        problem.solution_count = Some(scrape_solution_count(driver, options).await?);
+    }
+
+    // Similar questions of premium problems are behind the paywall along with the description
+    if options.with_similar && description.is_some() {
+        problem.similar_questions = scrape_similar_questions(driver, options).await?;
+    }
+
+    if options.with_templates {
+        problem.code_templates = scrape_code_templates(driver, options, problem).await?;
+        if let (Some(lang), true) = (&options.template_lang, problem.code_templates.is_empty()) {
+            warn!(lang, "no code template found for the language");
+        }
+    }
+
+    if options.with_description || options.with_constraints || options.with_examples {
+        step(options, "Scraping the problem description").await;
+        let html = match description {
+            Some(description) => Some(description.inner_html().await?),
+            None => {
+                info!(
+                    url = problem.url,
+                    "skipping the description of a premium problem"
+                );
+                None
+            }
+        };
+
+        // Examples and constraints are parsed from the html, before it might be converted to Markdown
+        if let (true, Some(html)) = (options.with_examples, &html) {
+            problem.examples = parse_examples_html(html);
+        }
+        if let (true, Some(html)) = (options.with_constraints, &html) {
+            problem.constraints = parse_constraints_html(html);
+        }
+
+        if options.with_description {
+            problem.description_html = html.map(|html| match options.plain_text {
+                true => html_to_markdown(&html),
+                false => html,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Whole problemset scraping function. Paginates through the problems table collecting every row.
+#[instrument(skip_all)]
+pub async fn scrape_all<D: super::ScrapeDriver>(
+    driver: &D,
+    options: &ScrapeOptions,
+) -> Result<Vec<PotdInfo>, ScrapeError> {
+    let (table, layout) = open_problemset(driver, options).await?;
+    let table = &table;
+
+    // Total page count is the largest numbered pagination button
+    let mut total_pages = 1;
+    for button in driver.query("nav[role='navigation'] button").await? {
+        if let Ok(page) = button.text().await?.trim().parse::<u32>() {
+            total_pages = total_pages.max(page);
+        }
+    }
+    let pages = options
+        .max_pages
+        .map_or(total_pages, |max_pages| max_pages.min(total_pages));
+
+    let mut problems = Vec::new();
+    for page in 1..=pages {
+        step(options, &format!("Scraping problemset page {page}/{pages}")).await;
+
+        for row in table.query(PROBLEM_ROW).await? {
+            // POTD is pinned on top of every page, while also being listed on its own page
+            if !is_potd_row(&row).await? {
+                problems.push(
+                    parse_problem_row(&row, &layout, &options.selectors, options.domain()).await?,
+                );
+            }
+        }
+
+        if page == pages {
+            break;
+        }
+        if options.interrupted.load(Ordering::Relaxed) {
+            warn!(
+                page,
+                pages, "scraping interrupted, skipping the rest of the pages"
+            );
+            break;
+        }
+
+        let last_href = &problems.last().map(|problem| problem.url.clone());
+        let last_title_anchor = &format!(
+            "{PROBLEM_ROW}:last-child {}",
+            layout.title_anchor(&options.selectors)
+        );
+
+        step(options, "Navigating to the next problemset page").await;
+        driver
+            .find("nav[role='navigation'] button[aria-label='next']")
+            .await?
+            .click()
+            .await?;
+
+        // Wait for the table to replace the rows of the previous page
+        poll_until(
+            options,
+            "problems table to load the next page",
+            move || async move {
+                let Some(anchor) = table.query(last_title_anchor).await?.into_iter().next() else {
+                    return Ok(false);
+                };
+                let href = anchor
+                    .attr("href")
+                    .await?
+                    .map(|href| absolute_url(options.domain(), &href));
+                Ok(href != *last_href)
+            },
+        )
+        .instrument(debug_span!(
+            "hydration",
+            element = "rowgroup",
+            page = page + 1
+        ))
+        .await?;
+    }
+
+    Ok(problems)
+}
@@ -0,0 +1,79 @@
+use super::ScrapeError;
+use std::{fmt, str::FromStr};
+use thirtyfour::{Cookie, WebDriver};
+use tracing::info;
+
+// Cookie of a signed in session, which LeetCode sets on login along with the CSRF token cookie
+pub static SESSION_COOKIE: &str = "LEETCODE_SESSION";
+
+/**
+Cookies of a signed in session, which give access to the premium problems with a premium account.
+Values are never shown, not even in the debug representation, so that they don't end up in any log.
+*/
+#[derive(Clone)]
+pub struct SessionCookies(Vec<(String, String)>);
+
+impl SessionCookies {
+    /// Names of the cookies, in the order they were given.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|(name, _)| name.as_str())
+    }
+}
+
+impl fmt::Debug for SessionCookies {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.names().map(|name| (name, "<redacted>")))
+            .finish()
+    }
+}
+
+impl FromStr for SessionCookies {
+    type Err = String;
+
+    /**
+    Parses the cookies as a Cookie header copied from the browser ( `LEETCODE_SESSION=...; csrftoken=...` ),
+    where the pairs can be separated by newlines as well. Errors only ever name the cookies, and not their values.
+    */
+    fn from_str(header: &str) -> Result<Self, Self::Err> {
+        let mut cookies = Vec::new();
+        for pair in header.split([';', '\n']).map(str::trim) {
+            if pair.is_empty() {
+                continue;
+            }
+
+            match pair.split_once('=') {
+                Some((name, value)) if !name.trim().is_empty() && !value.trim().is_empty() => {
+                    cookies.push((name.trim().to_string(), value.trim().to_string()))
+                }
+                Some((name, _)) => return Err(format!("cookie '{}' has no value", name.trim())),
+                None => return Err("expected cookies as name=value pairs".to_string()),
+            }
+        }
+
+        match cookies.iter().any(|(name, _)| name == SESSION_COOKIE) {
+            true => Ok(Self(cookies)),
+            false => Err(format!("expected a {SESSION_COOKIE} cookie")),
+        }
+    }
+}
+
+/**
+Signs the WebDriver session in by adding the session cookies for the domain, before any problem page is visited.
+Cookies can only be added for the domain of the open page, and so the domain is navigated to first.
+*/
+pub async fn add_session_cookies(
+    driver: &WebDriver,
+    domain: &str,
+    cookies: &SessionCookies,
+) -> Result<(), ScrapeError> {
+    driver.goto(domain).await?;
+    for (name, value) in &cookies.0 {
+        let mut cookie = Cookie::new(name.clone(), value.clone());
+        cookie.set_path("/");
+        driver.add_cookie(cookie).await?;
+    }
+
+    info!(cookies = ?cookies.names().collect::<Vec<_>>(), "added session cookies");
+    Ok(())
+}
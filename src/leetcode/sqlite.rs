@@ -0,0 +1,89 @@
+use super::{Difficulty, PotdInfo};
+use rusqlite::{params, Connection};
+use std::{error::Error, path::Path};
+
+// Problems are keyed by their number, so that scraping a problem again updates it in place.
+// Details of the problem page are kept as the JSON of the whole problem, as they don't fit in columns
+static SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS problems (
+        number INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        slug TEXT NOT NULL,
+        url TEXT NOT NULL,
+        difficulty TEXT NOT NULL,
+        acceptance_rate REAL NOT NULL,
+        is_premium INTEGER NOT NULL,
+        solution_url TEXT,
+        points INTEGER,
+        problem TEXT NOT NULL,
+        first_scraped TEXT NOT NULL,
+        last_scraped TEXT NOT NULL
+    );
+";
+
+static UPSERT_PROBLEM: &str = "
+    INSERT INTO problems (
+        number, name, slug, url, difficulty, acceptance_rate, is_premium, solution_url, points,
+        problem, first_scraped, last_scraped
+    )
+    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?11)
+    ON CONFLICT (number) DO UPDATE SET
+        name = excluded.name,
+        slug = excluded.slug,
+        url = excluded.url,
+        difficulty = excluded.difficulty,
+        acceptance_rate = excluded.acceptance_rate,
+        is_premium = excluded.is_premium,
+        solution_url = excluded.solution_url,
+        points = excluded.points,
+        problem = excluded.problem,
+        last_scraped = excluded.last_scraped;
+";
+
+fn difficulty_label(difficulty: &Difficulty) -> &'static str {
+    match difficulty {
+        Difficulty::Easy => "Easy",
+        Difficulty::Medium => "Medium",
+        Difficulty::Hard => "Hard",
+    }
+}
+
+/**
+Upserts the problems into the `problems` table of the SQLite database, which is created along with
+its schema if absent. Problems are keyed by their number, and so a history of them is built up without duplicates,
+recording the dates they were first and last scraped on. Returns the number of problems upserted.
+*/
+pub fn upsert_problems<'a>(
+    path: &Path,
+    date: &str,
+    problems: impl IntoIterator<Item = &'a PotdInfo>,
+) -> Result<usize, Box<dyn Error>> {
+    let mut connection = Connection::open(path)?;
+    connection.execute_batch(SCHEMA)?;
+
+    // Problems are upserted all at once, so that a failure doesn't leave only some of them updated
+    let transaction = connection.transaction()?;
+    let mut upserted = 0;
+    for problem in problems {
+        transaction.execute(
+            UPSERT_PROBLEM,
+            params![
+                problem.number,
+                problem.name,
+                problem.slug,
+                problem.url,
+                difficulty_label(&problem.difficulty),
+                f64::from(problem.acceptance_rate),
+                problem.is_premium,
+                problem.solution_url,
+                problem.points,
+                serde_json::to_string(problem)?,
+                date,
+            ],
+        )?;
+        upserted += 1;
+    }
+    transaction.commit()?;
+
+    Ok(upserted)
+}
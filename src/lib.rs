@@ -0,0 +1,7 @@
+/*!
+Scrapers for the LeetCode problem of the day and the links between Wikipedia pages.
+The binaries are thin command line wrappers over these modules.
+*/
+
+pub mod leetcode;
+pub mod wiki;
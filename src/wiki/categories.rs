@@ -0,0 +1,39 @@
+use scraper::{Html, Selector};
+
+// Categories are listed at the bottom of the page, with the hidden maintenance ones kept apart
+static NORMAL_CATEGORY: &str = "#mw-normal-catlinks ul li a";
+static HIDDEN_CATEGORY: &str = "#mw-hidden-catlinks ul li a";
+
+/// Names of the categories listed by the selector, in the order they are shown in.
+fn category_names(document: &Html, selector: &'static str) -> Vec<String> {
+    let selector = Selector::parse(selector).expect("to be a valid selector");
+    document
+        .select(&selector)
+        .map(|anchor| anchor.text().collect::<String>().trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Category names of the page, along with its hidden maintenance categories.
+pub(super) fn page_categories(document: &Html) -> (Vec<String>, Vec<String>) {
+    (
+        category_names(document, NORMAL_CATEGORY),
+        category_names(document, HIDDEN_CATEGORY),
+    )
+}
+
+/**
+Collects the category names of the wiki page html document ( eg. Programming languages ), for
+clustering articles by topic. Hidden maintenance categories ( eg. Articles with short description )
+are only included on request, after the normal ones. Pages without any categories have none listed.
+*/
+pub fn fetch_categories(html: &str, include_hidden: bool) -> Vec<String> {
+    let document = Html::parse_document(html);
+
+    let (mut categories, hidden) = page_categories(&document);
+    if include_hidden {
+        categories.extend(hidden);
+    }
+
+    categories
+}
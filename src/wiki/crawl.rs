@@ -0,0 +1,478 @@
+use super::{
+    categories::page_categories, is_disambiguation, normalize_graph, normalize_wiki_ref,
+    parse_page_title, parse_wiki_links, FetchError, Fetcher, LinkFilter, PageMeta, RobotsCache,
+    WIKI_REGEX,
+};
+use futures::stream::{FuturesUnordered, StreamExt};
+use regex::Regex;
+use scraper::Html;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    error::Error,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+use tokio::{io::AsyncWriteExt, sync::Semaphore};
+use tracing::{debug, info, info_span, warn, Instrument};
+
+// Concurrent page fetches are kept low by default, to be polite to Wikipedia
+pub static DEFAULT_CONCURRENCY: usize = 4;
+
+// Crawl progress is logged at most this often, under the `progress` target
+static PROGRESS_INTERVAL: Duration = Duration::from_secs(2);
+
+// Crawls are checkpointed at most this often, when a state file is given
+static CHECKPOINT_INTERVAL: Duration = Duration::from_secs(10);
+
+// Upper bound on the pages fetched in a single crawl by default, to avoid runaway crawls
+pub static MAX_CRAWL_PAGES: usize = 100;
+
+/**
+Page fetched in a crawl, along with its display title and its depth as the hops away from the start page,
+and the categories it is listed under, apart from its hidden maintenance categories.
+Response metadata of the fetch is recorded with the page as well.
+*/
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CrawledPage {
+    pub page: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub depth: usize,
+    pub links: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hidden_categories: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<PageMeta>,
+}
+
+/// Link graph of the crawled pages, mapping every page to its outbound links.
+pub fn link_graph(pages: &[CrawledPage]) -> HashMap<String, Vec<String>> {
+    pages
+        .iter()
+        .map(|page| (page.page.clone(), page.links.clone()))
+        .collect()
+}
+
+/**
+Checkpoint of a crawl, with the pages fetched so far along with the visited pages and the ones still
+queued ( including the ones in flight when it was written ), so that an interrupted crawl can be resumed.
+*/
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct CrawlState {
+    pub start: String,
+    pub visited: HashSet<String>,
+    pub queue: VecDeque<(String, usize)>,
+    pub pages: Vec<CrawledPage>,
+}
+
+impl CrawlState {
+    /// Crawl state of a crawl which only has the start page queued.
+    pub fn new(start: &str) -> Self {
+        Self {
+            start: start.to_string(),
+            visited: HashSet::from([start.to_string()]),
+            queue: VecDeque::from([(start.to_string(), 0)]),
+            pages: Vec::new(),
+        }
+    }
+
+    /// Loads the crawl state checkpointed to the JSON state file.
+    pub async fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let content = tokio::fs::read_to_string(path).await?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /**
+    Checkpoints the crawl state to the JSON state file. It is written to a temporary file next to it first,
+    which is then renamed over the state file, so that a crash while writing never corrupts the checkpoint.
+    */
+    pub async fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+
+        let mut file = tokio::fs::File::create(&temp_path).await?;
+        file.write_all(serde_json::to_string(self)?.as_bytes())
+            .await?;
+        file.sync_all().await?;
+        tokio::fs::rename(&temp_path, path).await?;
+        Ok(())
+    }
+}
+
+/// Options of a crawl, which default to fetching only the start page.
+pub struct CrawlOptions {
+    /// Pages crawled, as hops away from the start page, where 1 fetches only the start page itself.
+    pub depth: usize,
+    /// Pages fetched at the same time at most.
+    pub concurrency: usize,
+    /// Filter of the links followed, which are always deduped.
+    pub filter: LinkFilter,
+    /// Pages fetched at most, whatever the depth.
+    pub limit: usize,
+    /// Links are only followed into articles whose name ( eg. `Rust_(programming_language)` ) matches any of these, when given.
+    pub include_patterns: Vec<Regex>,
+    /// Links are never followed into articles whose name matches any of these.
+    pub exclude_patterns: Vec<Regex>,
+    /// State file the crawl is checkpointed to, every so often and once it stops.
+    pub checkpoint: Option<PathBuf>,
+    /// Checkpointed state the crawl is resumed from, instead of starting over.
+    pub resume: Option<CrawlState>,
+}
+
+impl Default for CrawlOptions {
+    fn default() -> Self {
+        Self {
+            depth: 1,
+            concurrency: DEFAULT_CONCURRENCY,
+            filter: LinkFilter::default(),
+            limit: MAX_CRAWL_PAGES,
+            include_patterns: Vec::new(),
+            exclude_patterns: Vec::new(),
+            checkpoint: None,
+            resume: None,
+        }
+    }
+}
+
+/// Checks if the link is to be followed, as per the article name patterns of the crawl.
+fn is_followed(link: &str, include_patterns: &[Regex], exclude_patterns: &[Regex]) -> bool {
+    let name = WIKI_REGEX
+        .captures(link)
+        .and_then(|captures| captures.name("name"))
+        .map_or(link, |name| name.as_str());
+
+    (include_patterns.is_empty()
+        || include_patterns
+            .iter()
+            .any(|pattern| pattern.is_match(name)))
+        && !exclude_patterns
+            .iter()
+            .any(|pattern| pattern.is_match(name))
+}
+
+/// Writes the checkpoint of the crawl, where a failure is only warned about so that the crawl itself goes on.
+async fn checkpoint(
+    path: &Path,
+    start: &str,
+    visited: &HashSet<String>,
+    pending: &HashMap<String, usize>,
+    queue: &VecDeque<(String, usize)>,
+    crawled: &HashMap<String, CrawledPage>,
+) {
+    // Pages in flight are queued again, ahead of the rest as they are the closest to the start page
+    let mut in_flight = pending
+        .iter()
+        .map(|(page, &hops)| (page.clone(), hops))
+        .collect::<Vec<_>>();
+    in_flight.sort_by(|a, b| (a.1, &a.0).cmp(&(b.1, &b.0)));
+
+    let state = CrawlState {
+        start: start.to_string(),
+        visited: visited.clone(),
+        queue: in_flight.into_iter().chain(queue.iter().cloned()).collect(),
+        pages: crawled.values().cloned().collect(),
+    };
+    match state.save(path).await {
+        Ok(()) => debug!(path = %path.display(), pages = state.pages.len(), "checkpointed crawl"),
+        Err(err) => warn!(path = %path.display(), "could not checkpoint crawl ( {err} )"),
+    }
+}
+
+/**
+Breadth-first crawl starting from the page `start`, fetching the links of every page up
+to `depth` hops away. A depth of 1 fetches only the start page itself.
+At most `concurrency` pages are fetched at the same time, and their links are always deduped.
+The crawl stops queuing pages once `limit` pages have been fetched, whatever the depth,
+or once `interrupted` is set, in which case the pages in flight are still awaited.
+Progress of the crawl is logged every couple of seconds, under the `progress` target.
+Links are only followed into the articles whose name matches the include patterns ( if any ) and
+none of the exclude patterns, while the start page is always fetched.
+The crawl is checkpointed to the `checkpoint` state file every 10 seconds and once it stops, and is
+resumed from the `resume` state when given, which must be of a crawl from the same start page.
+Returns every fetched page with its outbound links, without any duplicate edges or self-loops,
+ordered by their depth and then their path.
+Disambiguation pages are left out of the map, unless it is the start page itself.
+*/
+pub async fn crawl(
+    fetcher: &Fetcher,
+    start: &str,
+    options: CrawlOptions,
+    interrupted: &AtomicBool,
+) -> Result<Vec<CrawledPage>, Box<dyn Error>> {
+    let CrawlOptions {
+        depth,
+        concurrency,
+        filter,
+        limit,
+        include_patterns,
+        exclude_patterns,
+        checkpoint: checkpoint_path,
+        resume,
+    } = options;
+    let CrawlState {
+        mut visited,
+        mut queue,
+        pages,
+        ..
+    } = match resume {
+        Some(state) if state.start != start => {
+            return Err(format!(
+                "checkpoint is of a crawl from '{}', not from '{start}'",
+                state.start
+            )
+            .into())
+        }
+        Some(state) => state,
+        None => CrawlState::new(start),
+    };
+
+    // Pages of a resumed crawl count towards the limit as well
+    let mut crawled = pages
+        .into_iter()
+        .map(|page| (page.page.clone(), page))
+        .collect::<HashMap<_, _>>();
+    let mut fetched_pages = crawled.len();
+    let semaphore = Semaphore::new(concurrency);
+    let mut pending = HashMap::new();
+    let mut last_progress = Instant::now();
+    let mut last_checkpoint = Instant::now();
+    let mut robots = RobotsCache::new();
+    let mut in_flight = FuturesUnordered::new();
+
+    loop {
+        // Pages are marked visited when queued, so a page is never fetched twice concurrently
+        // Queued pages are kept once interrupted or at the limit, so that a resumed crawl picks them up
+        while !interrupted.load(Ordering::Relaxed) && fetched_pages < limit {
+            let Some((page, hops)) = queue.pop_front() else {
+                break;
+            };
+
+            if !robots.is_allowed(fetcher, &page).await? {
+                info!(page, "skipping page disallowed by robots.txt");
+                continue;
+            }
+
+            fetched_pages += 1;
+            pending.insert(page.clone(), hops);
+            let semaphore = &semaphore;
+            in_flight.push(async move {
+                let _permit = semaphore
+                    .acquire()
+                    .await
+                    .expect("Semaphore is never closed");
+                let fetched = fetcher
+                    .fetch_page(&page)
+                    .instrument(info_span!("fetch", page, hops))
+                    .await;
+                (page, hops, fetched)
+            });
+        }
+
+        if let Some(path) = &checkpoint_path {
+            if last_checkpoint.elapsed() >= CHECKPOINT_INTERVAL {
+                last_checkpoint = Instant::now();
+                checkpoint(path, start, &visited, &pending, &queue, &crawled).await;
+            }
+        }
+
+        let Some((page, hops, fetched)) = in_flight.next().await else {
+            break;
+        };
+        pending.remove(&page);
+
+        // Dead links and timed out pages are skipped, while other request failures still abort the crawl
+        let fetched = match fetched {
+            Ok(fetched) => fetched,
+            Err(
+                err @ (FetchError::NotFound(_) | FetchError::Http(..) | FetchError::Timeout(_)),
+            ) => {
+                warn!(page, "skipping page ( {err} )");
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        // Redirected pages are recorded under the path they resolved to
+        let page = match fetched.url_ref != page {
+            true => {
+                visited.insert(fetched.url_ref.clone());
+                fetched.url_ref
+            }
+            false => page,
+        };
+
+        // Links of disambiguation pages are not related to each other, and so would pollute the graph
+        let document = Html::parse_document(&fetched.html);
+        if is_disambiguation(&document) {
+            match hops {
+                0 => warn!(page, "start page is a disambiguation page"),
+                _ => {
+                    info!(page, "skipping disambiguation page");
+                    continue;
+                }
+            }
+        }
+
+        let filter = LinkFilter {
+            dedup: true,
+            ..filter.clone()
+        };
+        // Pages which can't be parsed ( eg. without any main content ) are skipped like the dead links
+        let links = match parse_wiki_links(&fetched.html, filter) {
+            Ok(links) => links.into_iter().map(|link| link.href).collect::<Vec<_>>(),
+            Err(err) => {
+                warn!(page, "skipping page ( {err} )");
+                continue;
+            }
+        };
+
+        // Links are only followed if they would still be within the depth, and into the articles asked for
+        // Links which are not followed are still kept in the graph
+        if hops + 1 < depth {
+            for link in &links {
+                if is_followed(link, &include_patterns, &exclude_patterns)
+                    && visited.insert(link.clone())
+                {
+                    queue.push_back((link.clone(), hops + 1));
+                }
+            }
+        }
+
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            last_progress = Instant::now();
+            info!(
+                target: "progress",
+                fetched = crawled.len() + 1,
+                pending = in_flight.len(),
+                depth = hops,
+                "crawling"
+            );
+        }
+
+        let (categories, hidden_categories) = page_categories(&document);
+        crawled.insert(
+            page.clone(),
+            CrawledPage {
+                title: parse_page_title(&document),
+                page,
+                depth: hops,
+                links,
+                categories,
+                hidden_categories,
+                meta: Some(fetched.meta),
+            },
+        );
+    }
+
+    if let Some(path) = &checkpoint_path {
+        checkpoint(path, start, &visited, &pending, &queue, &crawled).await;
+    }
+
+    match interrupted.load(Ordering::Relaxed) {
+        true if !queue.is_empty() => info!(
+            skipped_pages = queue.len(),
+            "crawl interrupted, skipping the queued pages"
+        ),
+        false if !queue.is_empty() => warn!(
+            fetched_pages,
+            skipped_pages = queue.len(),
+            "crawl limit reached, skipping the rest of the queued pages"
+        ),
+        _ => {}
+    }
+
+    let mut pages = crawled.into_values().collect::<Vec<_>>();
+    let mut graph = normalize_graph(link_graph(&pages));
+    for page in &mut pages {
+        page.links = graph
+            .remove(&page.page)
+            .expect("for every page to be in the graph");
+    }
+    pages.sort_by(|a, b| (a.depth, &a.page).cmp(&(b.depth, &b.page)));
+
+    Ok(pages)
+}
+
+/// Links of a batch of pages, along with the pages which failed and why, so that one failure doesn't abort the whole batch.
+#[derive(Default)]
+pub struct BatchLinks {
+    pub links: HashMap<String, Vec<String>>,
+    pub errors: Vec<(String, Box<dyn Error>)>,
+}
+
+/**
+Fetches the links of every page in `refs` concurrently, with at most `concurrency` pages fetched at the same time.
+Pages are recorded under the path they resolved to, and their links are always deduped.
+*/
+pub async fn fetch_many(
+    fetcher: &Fetcher,
+    refs: &[String],
+    concurrency: usize,
+    filter: LinkFilter,
+) -> BatchLinks {
+    let semaphore = Semaphore::new(concurrency);
+    let filter = LinkFilter {
+        dedup: true,
+        ..filter
+    };
+
+    let mut batch = BatchLinks::default();
+    let mut robots = RobotsCache::new();
+    let mut in_flight = FuturesUnordered::new();
+
+    for url_ref in refs {
+        let page = match normalize_wiki_ref(url_ref) {
+            Ok(page) => page,
+            Err(err) => {
+                batch.errors.push((url_ref.clone(), err.into()));
+                continue;
+            }
+        };
+
+        // Disallowed pages are reported as failures, as they were explicitly asked for
+        match robots.is_allowed(fetcher, &page).await {
+            Ok(true) => {}
+            Ok(false) => {
+                let err = format!("page '{page}' is disallowed by robots.txt");
+                batch.errors.push((url_ref.clone(), err.into()));
+                continue;
+            }
+            Err(err) => {
+                batch.errors.push((url_ref.clone(), err));
+                continue;
+            }
+        }
+
+        let (semaphore, filter) = (&semaphore, &filter);
+        in_flight.push(async move {
+            let _permit = semaphore
+                .acquire()
+                .await
+                .expect("Semaphore is never closed");
+
+            let fetched = async {
+                let page = fetcher.fetch_page(&page).await?;
+                let links = parse_wiki_links(&page.html, filter.clone())?;
+                Ok::<_, Box<dyn Error>>((page.url_ref, links))
+            }
+            .instrument(info_span!("fetch", page = url_ref))
+            .await;
+            (url_ref, fetched)
+        });
+    }
+
+    while let Some((url_ref, fetched)) = in_flight.next().await {
+        match fetched {
+            Ok((page, links)) => {
+                let links = links.into_iter().map(|link| link.href).collect();
+                batch.links.insert(page, links);
+            }
+            Err(err) => batch.errors.push((url_ref.clone(), err)),
+        }
+    }
+
+    batch
+}
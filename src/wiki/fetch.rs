@@ -0,0 +1,384 @@
+use reqwest::{header::RETRY_AFTER, Client, Proxy, StatusCode};
+use std::{error::Error, fmt, path::PathBuf, time::Duration};
+use tokio::{sync::Mutex, time::Instant};
+use tracing::{debug, warn};
+
+// Cached pages are refetched after a day by default
+pub static DEFAULT_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+// Requests are spaced out by default, to be polite to Wikipedia
+pub static DEFAULT_REQUEST_DELAY: Duration = Duration::from_millis(200);
+
+// Requests are given up on after a while, so that a hung connection doesn't stall the whole crawl
+pub static DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+static MAX_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Idle connections are kept for twice the default concurrency, and dropped after 90 seconds ( as reqwest does )
+pub static DEFAULT_POOL_MAX_IDLE_PER_HOST: usize = 8;
+pub static DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+// Transient responses are retried after 1, 2, 4... seconds, unless the server asks for a longer wait
+static RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+static MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+// Keep-alive probes stop NATs and proxies from silently dropping the pooled connections
+static TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+
+// User agent identifying the crawler, as asked by the Wikipedia etiquette guidelines
+pub static DEFAULT_USER_AGENT: &str = concat!(
+    "scraprs/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/Ritvik-Gupta/scraprs)"
+);
+
+/// Connection pool tuning of the HTTP client, for the reuse of connections across a large crawl.
+pub struct PoolOptions {
+    /// Idle connections kept open per host, which would be at least the concurrency to be reused.
+    pub max_idle_per_host: usize,
+    /// Idle connections are closed after this long, where `None` keeps them open indefinitely.
+    pub idle_timeout: Option<Duration>,
+}
+
+impl Default for PoolOptions {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: DEFAULT_POOL_MAX_IDLE_PER_HOST,
+            idle_timeout: Some(DEFAULT_POOL_IDLE_TIMEOUT),
+        }
+    }
+}
+
+/**
+Builds the HTTP client shared across all the page fetches, so that connections are pooled.
+Requests are routed through the proxy when one is given ( either an http(s) or a socks5 url ),
+and otherwise through the proxy of the `HTTP_PROXY` / `HTTPS_PROXY` env variables, if any.
+Every request times out after the `timeout`, and connecting times out after at most 10 seconds of it.
+Connections are pooled as tuned by the pool options, and kept alive with TCP keep-alive probes.
+*/
+pub fn build_client(
+    user_agent: &str,
+    proxy: Option<&str>,
+    timeout: Duration,
+    pool: &PoolOptions,
+) -> reqwest::Result<Client> {
+    let mut builder = Client::builder()
+        .user_agent(user_agent)
+        .timeout(timeout)
+        .connect_timeout(timeout.min(MAX_CONNECT_TIMEOUT))
+        .pool_max_idle_per_host(pool.max_idle_per_host)
+        .pool_idle_timeout(pool.idle_timeout)
+        .tcp_keepalive(TCP_KEEPALIVE);
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(Proxy::all(proxy)?);
+    }
+    builder.build()
+}
+
+/// 64-bit FNV-1a hash, which unlike the std hasher is stable across runs and Rust versions.
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Cached page html, along with the url it resolved to after redirects when it was fetched.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct CachedPage {
+    pub final_url: String,
+    pub html: String,
+}
+
+/// On-disk cache of fetched pages, keyed by a hash of their url and expiring after the `ttl`.
+pub struct PageCache {
+    pub dir: PathBuf,
+    pub ttl: Duration,
+}
+
+impl PageCache {
+    fn path(&self, url: &str) -> PathBuf {
+        self.dir.join(format!("{:016x}.json", fnv1a(url)))
+    }
+
+    /// Cached page, if it was cached and has not expired yet.
+    pub async fn get(&self, url: &str) -> Option<CachedPage> {
+        let path = self.path(url);
+        let modified = tokio::fs::metadata(&path).await.ok()?.modified().ok()?;
+        if modified.elapsed().ok()? > self.ttl {
+            return None;
+        }
+
+        let content = tokio::fs::read_to_string(path).await.ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub async fn put(&self, url: &str, page: &CachedPage) -> std::io::Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let content = serde_json::to_string(page).expect("to serialize a cached page");
+        tokio::fs::write(self.path(url), content).await
+    }
+}
+
+/**
+Politeness limiter, spacing out the requests by at least the `delay`.
+Requests of concurrent fetches queue up on the limiter, and so the delay holds under any concurrency.
+*/
+pub struct RateLimiter {
+    delay: Duration,
+    next_request: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            next_request: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Waits until the next request is allowed to be made.
+    pub async fn wait(&self) {
+        let mut next_request = self.next_request.lock().await;
+        tokio::time::sleep_until(*next_request).await;
+        *next_request = Instant::now() + self.delay;
+    }
+}
+
+/// Errors that can occur while fetching a page, distinguishing dead links from failures.
+#[derive(Debug)]
+pub enum FetchError {
+    NotFound(String),
+    Http(StatusCode, String),
+    Timeout(String),
+    Request(reqwest::Error),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(url) => write!(f, "page '{url}' was not found"),
+            Self::Http(status, url) => write!(f, "page '{url}' responded with {status}"),
+            Self::Timeout(url) => write!(f, "page '{url}' timed out"),
+            Self::Request(err) => write!(f, "request failure: {err}"),
+            Self::Io(err) => write!(f, "page cache failure: {err}"),
+        }
+    }
+}
+
+impl Error for FetchError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Request(err) => Some(err),
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for FetchError {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Request(err)
+    }
+}
+
+impl From<std::io::Error> for FetchError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Checks if the response status is a transient failure ( rate limiting or a server error ), worth retrying.
+fn is_transient(status: StatusCode) -> bool {
+    matches!(status.as_u16(), 429 | 500..=503)
+}
+
+/**
+Parses the value of a `Retry-After` header, as either the seconds to wait or the http date to wait until.
+Dates in the past are taken as no wait at all.
+*/
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+/// Response metadata of a fetched page, for diagnosing why a page has unexpected links ( eg. a redirect ).
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PageMeta {
+    /// Url the page resolved to after redirects.
+    pub final_url: String,
+    /// Status of the response, which cached pages don't have.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Length of the page html in bytes.
+    pub content_length: usize,
+    /// Time taken by the fetch in milliseconds, including any retries.
+    pub duration_ms: u64,
+    pub cached: bool,
+}
+
+/// Fetched page html, along with the relative path it resolved to after redirects.
+pub struct FetchedPage {
+    pub url_ref: String,
+    pub html: String,
+    pub meta: PageMeta,
+}
+
+/// Page fetcher, which shares the HTTP client and looks up the page cache when one is present.
+/// Relative page paths are resolved against the `base_url` of the wiki domain.
+pub struct Fetcher {
+    pub client: Client,
+    pub base_url: String,
+    pub cache: Option<PageCache>,
+    /// Cached pages are never requested, and so are not rate limited.
+    pub rate_limiter: Option<RateLimiter>,
+    /// Timed out requests are retried this many times, before the page is given up on.
+    pub timeout_retries: u32,
+    /// Transient responses ( 429 and 500 to 503 ) are retried this many times with an exponential backoff,
+    /// while a missing page ( 404 ) is never retried.
+    pub max_retries: u32,
+}
+
+impl Fetcher {
+    /// Fetches the html of the page at the relative path `url_ref`, following any redirects,
+    /// along with the metadata of the response.
+    pub async fn fetch_page(&self, url_ref: &str) -> Result<FetchedPage, FetchError> {
+        let url = format!("{}{url_ref}", self.base_url);
+        let started = Instant::now();
+
+        // Cached pages are stored under the requested path, along with the url they resolved to
+        if let Some(cache) = &self.cache {
+            if let Some(CachedPage { final_url, html }) = cache.get(&url).await {
+                debug!(url, "page cache hit");
+                let url_ref = match reqwest::Url::parse(&final_url) {
+                    Ok(resolved_url) => self.resolved_ref(&resolved_url, url_ref),
+                    Err(_) => url_ref.to_string(),
+                };
+                return Ok(FetchedPage {
+                    url_ref,
+                    meta: PageMeta {
+                        final_url,
+                        status: None,
+                        content_length: html.len(),
+                        duration_ms: started.elapsed().as_millis() as u64,
+                        cached: true,
+                    },
+                    html,
+                });
+            }
+        }
+
+        let (mut timeouts, mut retries) = (0, 0);
+        let (resolved_url, status, html) = loop {
+            match self.request(&url).await {
+                Err((FetchError::Timeout(_), _)) if timeouts < self.timeout_retries => {
+                    timeouts += 1;
+                    warn!(url, attempt = timeouts, "request timed out, retrying");
+                }
+                Err((FetchError::Http(status, _), retry_after))
+                    if is_transient(status) && retries < self.max_retries =>
+                {
+                    // Waits asked for by the server are honored, though capped so that a crawl never stalls for long
+                    let delay = retry_after
+                        .unwrap_or(RETRY_BASE_DELAY * 2u32.saturating_pow(retries))
+                        .min(MAX_RETRY_DELAY);
+                    retries += 1;
+                    warn!(
+                        url,
+                        %status,
+                        attempt = retries,
+                        delay_ms = delay.as_millis() as u64,
+                        "transient response, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                res => break res.map_err(|(err, _)| err)?,
+            }
+        };
+
+        let url_ref = self.resolved_ref(&resolved_url, url_ref);
+        let page = CachedPage {
+            final_url: resolved_url.to_string(),
+            html,
+        };
+        // Page is already fetched, and so a failed cache write only costs a refetch next time
+        if let Some(cache) = &self.cache {
+            if let Err(err) = cache.put(&url, &page).await {
+                warn!(url, "could not cache the page ( {err} )");
+            }
+        }
+        let CachedPage { final_url, html } = page;
+
+        Ok(FetchedPage {
+            url_ref,
+            meta: PageMeta {
+                final_url,
+                status: Some(status.as_u16()),
+                content_length: html.len(),
+                duration_ms: started.elapsed().as_millis() as u64,
+                cached: false,
+            },
+            html,
+        })
+    }
+
+    /// Relative path of the url the page resolved to, as renamed pages redirect to their new path,
+    /// which is kept if still on the wiki domain.
+    fn resolved_ref(&self, resolved_url: &reqwest::Url, url_ref: &str) -> String {
+        match resolved_url.as_str().starts_with(self.base_url.as_str()) {
+            true => resolved_url.path().to_string(),
+            false => url_ref.to_string(),
+        }
+    }
+
+    /**
+    Requests the page at the url, returning the url it resolved to along with its status and html.
+    Failures come along with the wait the server asked for before a retry ( with `Retry-After` ), if any.
+    */
+    async fn request(
+        &self,
+        url: &str,
+    ) -> Result<(reqwest::Url, StatusCode, String), (FetchError, Option<Duration>)> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.wait().await;
+        }
+
+        // Timeouts are told apart, as the page might still respond on a retry
+        let timeout = |err: reqwest::Error| match err.is_timeout() {
+            true => FetchError::Timeout(url.to_string()),
+            false => FetchError::Request(err),
+        };
+
+        debug!(url, "requesting page");
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|err| (timeout(err), None))?;
+        match response.status() {
+            StatusCode::NOT_FOUND => return Err((FetchError::NotFound(url.to_string()), None)),
+            status if !status.is_success() => {
+                let retry_after = response
+                    .headers()
+                    .get(RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(parse_retry_after);
+                return Err((FetchError::Http(status, url.to_string()), retry_after));
+            }
+            _ => {}
+        }
+
+        let (resolved_url, status) = (response.url().clone(), response.status());
+        let html = response.text().await.map_err(|err| (timeout(err), None))?;
+        Ok((resolved_url, status, html))
+    }
+}
@@ -0,0 +1,265 @@
+use super::WIKI_REGEX;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt::Write,
+};
+
+/// Quotes the text as a DOT string, escaping the characters special to it.
+fn dot_quote(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+/// Escapes the text for an XML attribute or element content.
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Human-readable article name of a wiki link, falling back to the link itself.
+fn article_name(link: &str) -> String {
+    WIKI_REGEX
+        .captures(link)
+        .and_then(|captures| captures.name("name"))
+        .map_or_else(|| link.to_string(), |name| name.as_str().replace('_', " "))
+}
+
+/**
+Normalizes the link graph, so that every edge appears once and no page links to itself.
+Links keep the order of their first occurrence on the page.
+*/
+pub fn normalize_graph(graph: HashMap<String, Vec<String>>) -> HashMap<String, Vec<String>> {
+    graph
+        .into_iter()
+        .map(|(page, links)| {
+            let mut seen = HashSet::new();
+            let links = links
+                .into_iter()
+                .filter(|link| *link != page && seen.insert(link.clone()))
+                .collect();
+            (page, links)
+        })
+        .collect()
+}
+
+/**
+Renders the link graph in the Graphviz DOT format, with each page as a node and each link as an edge.
+Nodes and edges are written in a sorted order, so that the output is reproducible.
+*/
+pub fn to_dot(graph: &HashMap<String, Vec<String>>) -> String {
+    let nodes = graph
+        .iter()
+        .flat_map(|(page, links)| std::iter::once(page).chain(links))
+        .collect::<BTreeSet<_>>();
+
+    let mut pages = graph.keys().collect::<Vec<_>>();
+    pages.sort();
+
+    let mut dot = String::from("digraph wiki {\n");
+    for node in nodes {
+        let (id, label) = (dot_quote(node), dot_quote(&article_name(node)));
+        writeln!(dot, "    {id} [label={label}];").expect("to write to a string");
+    }
+    for page in pages {
+        for link in &graph[page] {
+            let (source, target) = (dot_quote(page), dot_quote(link));
+            writeln!(dot, "    {source} -> {target};").expect("to write to a string");
+        }
+    }
+    dot.push_str("}\n");
+
+    dot
+}
+
+/**
+Renders the link graph in the GraphML format, for network analysis tools like Gephi or Cytoscape.
+Each page is a node identified by its link, with the article title as a `title` attribute.
+Nodes and edges are written in a sorted order, so that the output is reproducible.
+*/
+pub fn to_graphml(graph: &HashMap<String, Vec<String>>) -> String {
+    let nodes = graph
+        .iter()
+        .flat_map(|(page, links)| std::iter::once(page).chain(links))
+        .collect::<BTreeSet<_>>();
+
+    let mut pages = graph.keys().collect::<Vec<_>>();
+    pages.sort();
+
+    let mut graphml = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        "  <key id=\"title\" for=\"node\" attr.name=\"title\" attr.type=\"string\"/>\n",
+        "  <graph id=\"wiki\" edgedefault=\"directed\">\n",
+    ));
+    for node in nodes {
+        let (id, title) = (xml_escape(node), xml_escape(&article_name(node)));
+        writeln!(
+            graphml,
+            "    <node id=\"{id}\"><data key=\"title\">{title}</data></node>"
+        )
+        .expect("to write to a string");
+    }
+    for page in pages {
+        for link in &graph[page] {
+            let (source, target) = (xml_escape(page), xml_escape(link));
+            writeln!(
+                graphml,
+                "    <edge source=\"{source}\" target=\"{target}\"/>"
+            )
+            .expect("to write to a string");
+        }
+    }
+    graphml.push_str("  </graph>\n</graphml>\n");
+
+    graphml
+}
+
+/**
+Renders the link graph as a JSON object, mapping each page to the links on it in document order.
+Pages are written in a sorted order, so that the output of different runs can be diffed.
+*/
+pub fn to_json(graph: &HashMap<String, Vec<String>>) -> String {
+    let sorted = graph.iter().collect::<BTreeMap<_, _>>();
+    serde_json::to_string_pretty(&sorted).expect("to serialize a map of strings")
+}
+
+/**
+Renders the link graph as a flat edge list, with one `source<TAB>target` line per link.
+Edges are deduplicated and written in a sorted order, so that the output can be piped into `sort` / `uniq` or diffed.
+*/
+pub fn to_edgelist(graph: &HashMap<String, Vec<String>>) -> String {
+    let edges = graph
+        .iter()
+        .flat_map(|(page, links)| links.iter().map(move |link| (page, link)))
+        .collect::<BTreeSet<_>>();
+
+    let mut edgelist = String::new();
+    for (source, target) in edges {
+        writeln!(edgelist, "{source}\t{target}").expect("to write to a string");
+    }
+
+    edgelist
+}
+
+// PageRank is the share of a random walk spent on a page, which follows a link with this probability
+// and otherwise jumps to any page, iterated until the ranks settle or for at most this many rounds
+static PAGERANK_DAMPING: f64 = 0.85;
+static PAGERANK_ITERATIONS: usize = 100;
+static PAGERANK_TOLERANCE: f64 = 1e-9;
+
+/// Degree statistics of a page of the link graph, along with its PageRank when asked for.
+#[derive(Debug, serde::Serialize)]
+pub struct NodeStats {
+    pub node: String,
+    pub in_degree: usize,
+    pub out_degree: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagerank: Option<f64>,
+}
+
+/// Deduplicated edges of the link graph, keyed by their source page, along with every page linked to.
+fn adjacency(graph: &HashMap<String, Vec<String>>) -> BTreeMap<&str, BTreeSet<&str>> {
+    let mut adjacency = BTreeMap::<&str, BTreeSet<&str>>::new();
+    for (page, links) in graph {
+        adjacency.entry(page).or_default();
+        for link in links {
+            adjacency.entry(link).or_default();
+            adjacency
+                .get_mut(page.as_str())
+                .expect("page to be added")
+                .insert(link);
+        }
+    }
+
+    adjacency
+}
+
+/**
+Computes the PageRank of every page of the link graph, including the pages which are only linked to.
+Ranks of the pages without any links ( like the uncrawled ones ) are spread over every page, so that they add up to 1.
+*/
+pub fn pagerank(graph: &HashMap<String, Vec<String>>) -> HashMap<String, f64> {
+    let adjacency = adjacency(graph);
+    let nodes = adjacency.len();
+    if nodes == 0 {
+        return HashMap::new();
+    }
+
+    let mut ranks = adjacency
+        .keys()
+        .map(|&node| (node, 1.0 / nodes as f64))
+        .collect::<BTreeMap<_, _>>();
+    for _ in 0..PAGERANK_ITERATIONS {
+        let dangling = adjacency
+            .iter()
+            .filter(|(_, links)| links.is_empty())
+            .map(|(node, _)| ranks[node])
+            .sum::<f64>();
+        let base = (1.0 - PAGERANK_DAMPING + PAGERANK_DAMPING * dangling) / nodes as f64;
+
+        let mut next = adjacency
+            .keys()
+            .map(|&node| (node, base))
+            .collect::<BTreeMap<_, _>>();
+        for (node, links) in &adjacency {
+            for link in links {
+                *next.get_mut(link).expect("link to be a node") +=
+                    PAGERANK_DAMPING * ranks[node] / links.len() as f64;
+            }
+        }
+
+        let delta = next
+            .iter()
+            .map(|(node, rank)| (rank - ranks[node]).abs())
+            .sum::<f64>();
+        ranks = next;
+        if delta < PAGERANK_TOLERANCE {
+            break;
+        }
+    }
+
+    ranks
+        .into_iter()
+        .map(|(node, rank)| (node.to_string(), rank))
+        .collect()
+}
+
+/**
+Computes the in-degree and out-degree of every page of the link graph, counting every distinct link once.
+Pages are sorted by their in-degree and then their out-degree, most linked first, with the ties sorted by page.
+*/
+pub fn degree_stats(graph: &HashMap<String, Vec<String>>, with_pagerank: bool) -> Vec<NodeStats> {
+    let adjacency = adjacency(graph);
+    let mut in_degrees = HashMap::<&str, usize>::new();
+    for link in adjacency.values().flatten() {
+        *in_degrees.entry(link).or_insert(0) += 1;
+    }
+    let mut ranks = match with_pagerank {
+        true => pagerank(graph),
+        false => HashMap::new(),
+    };
+
+    let mut stats = adjacency
+        .iter()
+        .map(|(&node, links)| NodeStats {
+            node: node.to_string(),
+            in_degree: in_degrees.get(node).copied().unwrap_or(0),
+            out_degree: links.len(),
+            pagerank: ranks.remove(node),
+        })
+        .collect::<Vec<_>>();
+    stats.sort_by(|a, b| {
+        b.in_degree
+            .cmp(&a.in_degree)
+            .then_with(|| b.out_degree.cmp(&a.out_degree))
+            .then_with(|| a.node.cmp(&b.node))
+    });
+
+    stats
+}
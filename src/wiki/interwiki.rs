@@ -0,0 +1,58 @@
+use scraper::{ElementRef, Html, Selector};
+use std::collections::HashMap;
+
+// Language links are listed in the sidebar panel of the legacy skins, and in the language
+// dropdown of the newer ones, though every skin marks their list items as interlanguage links
+static LANGUAGE_LINK: &str =
+    "li.interlanguage-link a[href], #p-lang a[hreflang][href], #p-lang-btn a[hreflang][href]";
+static LANGUAGE_CLASS_PREFIX: &str = "interwiki-";
+
+/// Language code of the language link, as its `hreflang` or `lang`, or else as the class of its list item.
+fn language_code(anchor: ElementRef) -> Option<String> {
+    let item_code = || {
+        let item = anchor.parent().and_then(ElementRef::wrap)?;
+        item.value()
+            .classes()
+            .find_map(|class| class.strip_prefix(LANGUAGE_CLASS_PREFIX))
+    };
+
+    anchor
+        .value()
+        .attr("hreflang")
+        .or_else(|| anchor.value().attr("lang"))
+        .or_else(item_code)
+        .map(str::trim)
+        .filter(|code| !code.is_empty())
+        .map(str::to_string)
+}
+
+/**
+Collects the links to the same article in other languages, from the language panel of the wiki page
+html document, keyed by their language code ( eg. fr ). Protocol-relative links are taken as https
+ones, and only the first link of a language is kept when a skin lists it more than once.
+*/
+pub fn fetch_interwiki_links(html: &str) -> HashMap<String, String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse(LANGUAGE_LINK).expect("to be a valid selector");
+
+    let mut links = HashMap::new();
+    for anchor in document.select(&selector) {
+        let href = anchor.value().attr("href").unwrap_or_default();
+        let href = match href.strip_prefix("//") {
+            Some(href) => format!("https://{href}"),
+            None => href.to_string(),
+        };
+
+        let Ok(url) = reqwest::Url::parse(&href) else {
+            continue;
+        };
+        if !["http", "https"].contains(&url.scheme()) {
+            continue;
+        }
+        if let Some(code) = language_code(anchor) {
+            links.entry(code).or_insert(href);
+        }
+    }
+
+    links
+}
@@ -0,0 +1,380 @@
+use super::{
+    Fetcher, BODY_LINK_SELECTOR, CONTENT_SELECTOR, LIST_LINK_SELECTOR, NAMESPACE_REGEX,
+    PARAGRAPH_LINK_SELECTOR, WIKI_REGEX,
+};
+use crate::leetcode::selector;
+use scraper::{CaseSensitivity, Html, Selector};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    error::Error,
+    fmt,
+    str::FromStr,
+};
+
+/// Namespace of a wiki page, as the prefix of its name ( `Category:Programming_languages` ).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Namespace {
+    /// Articles, which have no namespace prefix.
+    Main,
+    Category,
+    /// Files, also linked to with the older `Image:` prefix.
+    File,
+    Help,
+    Template,
+    Portal,
+    /// Any other namespace, as its prefix ( eg. `Wikipedia` or a localized one like `Kategorie` ).
+    Other(String),
+}
+
+impl Namespace {
+    /// Classifies the page name ( the wiki path without its `/wiki/` prefix ) by its namespace prefix.
+    pub fn of(name: &str) -> Self {
+        let Some(captures) = NAMESPACE_REGEX.captures(name) else {
+            return Self::Main;
+        };
+
+        match &captures["namespace"] {
+            "Category" => Self::Category,
+            "File" | "Image" => Self::File,
+            "Help" => Self::Help,
+            "Template" => Self::Template,
+            "Portal" => Self::Portal,
+            namespace => Self::Other(namespace.to_string()),
+        }
+    }
+}
+
+impl FromStr for Namespace {
+    type Err = Infallible;
+
+    /// Parses the known namespaces case-insensitively, and any other namespace as its prefix as written.
+    fn from_str(namespace: &str) -> Result<Self, Self::Err> {
+        Ok(match namespace.to_lowercase().as_str() {
+            "main" | "article" => Self::Main,
+            "category" => Self::Category,
+            "file" | "image" => Self::File,
+            "help" => Self::Help,
+            "template" => Self::Template,
+            "portal" => Self::Portal,
+            _ => Self::Other(namespace.to_string()),
+        })
+    }
+}
+
+impl fmt::Display for Namespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Main => write!(f, "Main"),
+            Self::Category => write!(f, "Category"),
+            Self::File => write!(f, "File"),
+            Self::Help => write!(f, "Help"),
+            Self::Template => write!(f, "Template"),
+            Self::Portal => write!(f, "Portal"),
+            Self::Other(namespace) => write!(f, "{namespace}"),
+        }
+    }
+}
+
+/// Wiki link found on a page, along with its human-readable texts and the namespace it links into.
+#[derive(Debug, Clone)]
+pub struct WikiLink {
+    pub href: String,
+    pub title: String,
+    pub display_text: String,
+    pub namespace: Namespace,
+    /// Link is in the lead section ( before the first `<h2>` ), which is the most central to the topic.
+    pub in_lead: bool,
+}
+
+/// Error for a start ref which could not be normalized to a relative wiki path.
+#[derive(Debug)]
+pub struct InvalidWikiRef(pub String);
+
+impl fmt::Display for InvalidWikiRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' is not a Wikipedia page, expected a relative wiki path ( /wiki/Rust ), \
+             an article title ( Rust ) or a page url ( https://en.wikipedia.org/wiki/Rust )",
+            self.0
+        )
+    }
+}
+
+impl Error for InvalidWikiRef {}
+
+/**
+Normalizes a page reference to the relative wiki path the crawler works with.
+Full page urls are reduced to their path, and bare article titles get the `/wiki/` prefix.
+*/
+pub fn normalize_wiki_ref(input: &str) -> Result<String, InvalidWikiRef> {
+    let input = input.trim();
+
+    let url_ref = if input.starts_with("http://") || input.starts_with("https://") {
+        match reqwest::Url::parse(input) {
+            Ok(url) => url.path().to_string(),
+            Err(_) => return Err(InvalidWikiRef(input.to_string())),
+        }
+    } else if input.starts_with('/') {
+        input.to_string()
+    } else {
+        // Article titles are written with spaces, while their slugs use underscores
+        format!("/wiki/{}", input.replace(' ', "_"))
+    };
+
+    match WIKI_REGEX.is_match(&url_ref) {
+        true => Ok(url_ref),
+        false => Err(InvalidWikiRef(input.to_string())),
+    }
+}
+
+/**
+Parses a list of newline-separated page references, as read from a seed file, normalizing each one.
+Blank lines and `#` comments are ignored, while invalid references are returned apart along with
+their 1-based line number.
+*/
+pub fn parse_wiki_ref_list(content: &str) -> (Vec<String>, Vec<(usize, InvalidWikiRef)>) {
+    let (mut refs, mut invalid) = (Vec::new(), Vec::new());
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match normalize_wiki_ref(line) {
+            Ok(url_ref) => refs.push(url_ref),
+            Err(err) => invalid.push((index + 1, err)),
+        }
+    }
+
+    (refs, invalid)
+}
+
+/// Part of the main content of a page that links are collected from, trading precision for recall.
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
+pub enum LinkScope {
+    /// Links of the paragraphs, which are the most relevant ones.
+    #[default]
+    Paragraphs,
+    /// Links of the lists as well ( eg. the See also section ).
+    Lists,
+    /// Every link of the main content, including the infobox, tables and navigation boxes.
+    Body,
+}
+
+impl LinkScope {
+    fn selector(self) -> &'static Selector {
+        match self {
+            Self::Paragraphs => &PARAGRAPH_LINK_SELECTOR,
+            Self::Lists => &LIST_LINK_SELECTOR,
+            Self::Body => &BODY_LINK_SELECTOR,
+        }
+    }
+}
+
+impl FromStr for LinkScope {
+    type Err = String;
+
+    fn from_str(scope: &str) -> Result<Self, Self::Err> {
+        match scope {
+            "paragraphs" => Ok(Self::Paragraphs),
+            "lists" => Ok(Self::Lists),
+            "body" => Ok(Self::Body),
+            scope => Err(format!(
+                "unsupported link scope '{scope}', expected one of paragraphs, lists or body"
+            )),
+        }
+    }
+}
+
+/// Filter applied to the links found on a page.
+#[derive(Default, Clone)]
+pub struct LinkFilter {
+    /// Every link is only returned once, in the order it was first seen in.
+    pub dedup: bool,
+    /// Links to namespaced pages ( `File:`, `Category:`, `Help:`, etc. ) are kept as well.
+    pub include_namespaces: bool,
+    /// Only links into these namespaces are kept, whatever `include_namespaces` is.
+    pub namespaces: Option<HashSet<Namespace>>,
+    /// Section fragments ( `#History` ) are kept, so that links to different sections stay apart.
+    pub keep_fragments: bool,
+    /// Only the first links are returned, in document order, to keep crawls of list pages manageable.
+    pub max_links: Option<usize>,
+    /// Red links ( to articles which don't exist yet ) are kept as well, as the path the article would have.
+    pub include_redlinks: bool,
+    /// Part of the main content that the links are collected from, only the paragraphs by default.
+    pub scope: LinkScope,
+}
+
+/// Strips the query string, and the fragment unless kept, so that every article has a single href.
+fn strip_href(href: &str, keep_fragments: bool) -> String {
+    let (path, fragment) = match href.split_once('#') {
+        Some((path, fragment)) => (path, Some(fragment)),
+        None => (href, None),
+    };
+    let path = path.split_once('?').map_or(path, |(path, _)| path);
+
+    match fragment {
+        Some(fragment) if keep_fragments => format!("{path}#{fragment}"),
+        _ => path.to_string(),
+    }
+}
+
+/// Path of the article a red link points to, out of the `title` of its edit href ( `/w/index.php?title=...&action=edit` ).
+fn redlink_href(href: &str) -> Option<String> {
+    let url = reqwest::Url::parse("https://wikipedia.org")
+        .ok()?
+        .join(href)
+        .ok()?;
+    let (_, title) = url.query_pairs().find(|(key, _)| key == "title")?;
+    Some(format!("/wiki/{}", title.replace(' ', "_")))
+}
+
+/**
+Utility function that is used to parse the wiki page html document and filter out the relevant links.
+Hrefs are stripped of their query strings and fragments before being matched, as per the `filter`.
+*/
+pub fn parse_wiki_links(html: &str, filter: LinkFilter) -> Result<Vec<WikiLink>, Box<dyn Error>> {
+    let document = Html::parse_document(html);
+
+    // Find the main content div, which is common to the wiki pages of every language
+    let content_div = document
+        .select(&CONTENT_SELECTOR)
+        .next()
+        .ok_or("page has no main content div ( div#bodyContent )")?;
+
+    // Lead section is everything before the first heading, in document order
+    let lead = content_div
+        .descendants()
+        .take_while(|node| node.value().as_element().map(|element| element.name()) != Some("h2"))
+        .map(|node| node.id())
+        .collect::<HashSet<_>>();
+
+    // All the relevant content is inside the paragraphs ( unless scoped wider )
+    // and so we fetch all the anchors from it and extract their 'href' links
+    let mut seen = HashSet::new();
+    let links = content_div
+        .select(filter.scope.selector())
+        .filter_map(|link_tag| {
+            // Anchors are only selected along with their 'href', though one without it is skipped all the same
+            let href = link_tag.value().attr("href")?;
+
+            // Red links are marked with the `new` class, and would only 404 when crawled
+            let is_redlink = link_tag
+                .value()
+                .has_class("new", CaseSensitivity::CaseSensitive);
+            let href = match is_redlink {
+                true if !filter.include_redlinks => return None,
+                true => redlink_href(href)?,
+                false => strip_href(href, filter.keep_fragments),
+            };
+            let name = WIKI_REGEX.captures(&href)?.name("name")?.as_str();
+            let name = name.split_once('#').map_or(name, |(name, _)| name);
+            let namespace = Namespace::of(name);
+            let is_kept = match &filter.namespaces {
+                Some(namespaces) => namespaces.contains(&namespace),
+                None => filter.include_namespaces || namespace == Namespace::Main,
+            };
+            if !is_kept {
+                return None;
+            }
+
+            // Title attribute is usually the article name, which the slug is derived from
+            let title = link_tag
+                .value()
+                .attr("title")
+                .filter(|_| !is_redlink)
+                .map_or_else(|| name.replace('_', " "), str::to_string);
+
+            // Anchors wrapping an image have no text, and so fall back to the title
+            let display_text = link_tag.text().collect::<String>().trim().to_string();
+            let display_text = match display_text.is_empty() {
+                true => title.clone(),
+                false => display_text,
+            };
+
+            Some(WikiLink {
+                href,
+                title,
+                display_text,
+                namespace,
+                in_lead: lead.contains(&link_tag.id()),
+            })
+        })
+        .filter(|link| !filter.dedup || seen.insert(link.href.clone()))
+        .take(filter.max_links.unwrap_or(usize::MAX))
+        .collect();
+
+    Ok(links)
+}
+
+/**
+Counts how many times each article is linked to in the wiki page html document, for ranking relatedness.
+Links are filtered as by default, except that duplicates are counted instead of dropped.
+*/
+pub fn fetch_wiki_link_counts(html: &str) -> Result<HashMap<String, usize>, Box<dyn Error>> {
+    let mut counts = HashMap::new();
+    for link in parse_wiki_links(html, LinkFilter::default())? {
+        *counts.entry(link.href).or_insert(0) += 1;
+    }
+
+    Ok(counts)
+}
+
+/**
+Collects the external links of the wiki page html document, as cited in its references and external
+links sections, in the order they were first seen in. Protocol-relative links are taken as https ones,
+while links back to Wikipedia itself are left out.
+*/
+pub fn fetch_external_links(html: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let document = Html::parse_document(html);
+
+    let mut seen = HashSet::new();
+    let mut links = Vec::new();
+    for anchor in document.select(&selector("a.external[href]")) {
+        let href = anchor.value().attr("href").unwrap_or_default();
+        let href = match href.strip_prefix("//") {
+            Some(href) => format!("https://{href}"),
+            None => href.to_string(),
+        };
+
+        let Ok(url) = reqwest::Url::parse(&href) else {
+            continue;
+        };
+        let is_wikipedia = url
+            .host_str()
+            .is_some_and(|host| host == "wikipedia.org" || host.ends_with(".wikipedia.org"));
+        if ["http", "https"].contains(&url.scheme()) && !is_wikipedia && seen.insert(href.clone()) {
+            links.push(href);
+        }
+    }
+
+    Ok(links)
+}
+
+/**
+Utility function that is used to fetch the wiki page html document and filter out the relevant links.
+The argument `url_ref` is the relative path ( excluding the wiki domain ) to the page,
+though anything accepted by `normalize_wiki_ref` would work as well.
+*/
+pub async fn fetch_wiki_links(
+    fetcher: &Fetcher,
+    url_ref: &str,
+    filter: LinkFilter,
+) -> Result<Vec<WikiLink>, Box<dyn Error>> {
+    let url_ref = normalize_wiki_ref(url_ref)?;
+
+    let page = fetcher.fetch_page(&url_ref).await?;
+    parse_wiki_links(&page.html, filter)
+}
+
+/// Hrefs of the links of the wiki page, as fetched with `fetch_wiki_links`, for callers that only need the paths.
+pub async fn fetch_wiki_hrefs(
+    fetcher: &Fetcher,
+    url_ref: &str,
+    filter: LinkFilter,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let links = fetch_wiki_links(fetcher, url_ref, filter).await?;
+    Ok(links.into_iter().map(|link| link.href).collect())
+}
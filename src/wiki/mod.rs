@@ -0,0 +1,89 @@
+/*!
+Wikipedia page scraper for fetching all relevant links and connected wiki pages.
+Pages are referred to by their relative wiki paths, as connected page links are also relative.
+*/
+
+mod categories;
+mod crawl;
+mod fetch;
+mod graph;
+mod interwiki;
+mod links;
+mod robots;
+mod summary;
+mod verify;
+
+pub use categories::fetch_categories;
+pub use crawl::{
+    crawl, fetch_many, link_graph, BatchLinks, CrawlOptions, CrawlState, CrawledPage,
+    DEFAULT_CONCURRENCY, MAX_CRAWL_PAGES,
+};
+pub use fetch::{
+    build_client, parse_retry_after, CachedPage, FetchError, FetchedPage, Fetcher, PageCache,
+    PageMeta, PoolOptions, RateLimiter, DEFAULT_CACHE_TTL, DEFAULT_POOL_IDLE_TIMEOUT,
+    DEFAULT_POOL_MAX_IDLE_PER_HOST, DEFAULT_REQUEST_DELAY, DEFAULT_REQUEST_TIMEOUT,
+    DEFAULT_USER_AGENT,
+};
+pub use graph::{
+    degree_stats, normalize_graph, pagerank, to_dot, to_edgelist, to_graphml, to_json, NodeStats,
+};
+pub use interwiki::fetch_interwiki_links;
+pub use links::{
+    fetch_external_links, fetch_wiki_hrefs, fetch_wiki_link_counts, fetch_wiki_links,
+    normalize_wiki_ref, parse_wiki_links, parse_wiki_ref_list, InvalidWikiRef, LinkFilter,
+    LinkScope, Namespace, WikiLink,
+};
+pub use robots::{parse_disallow_rules, RobotsCache};
+pub use summary::{fetch_wiki_summary, is_disambiguation, parse_page_title, parse_wiki_summary};
+pub use verify::verify_links;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use scraper::Selector;
+use std::{error::Error, fmt};
+
+// Wikipedia reguar expression which can be used to match and filter wiki links
+pub static WIKI_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("^/wiki/(?P<name>[^\"]+)$").expect("To be a valid regex"));
+
+// Namespaced page names, like `Category:Programming_languages`, have a prefix before a colon
+// Article names which contain a colon are instead followed by a space, like `Star_Wars:_Episode_I`
+pub static NAMESPACE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("^(?P<namespace>[^:]+):[^_]").expect("To be a valid regex"));
+
+// Selectors of the links parser are compiled once, as a crawl parses thousands of pages with them
+pub static CONTENT_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("div#bodyContent").expect("To be a valid selector"));
+pub static PARAGRAPH_LINK_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("p a[href]").expect("To be a valid selector"));
+pub static LIST_LINK_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("p a[href], li a[href]").expect("To be a valid selector"));
+pub static BODY_LINK_SELECTOR: Lazy<Selector> =
+    Lazy::new(|| Selector::parse("a[href]").expect("To be a valid selector"));
+
+/// Error of a Wikipedia language code which isn't lowercase ASCII letters ( eg. en or fr ).
+#[derive(Debug)]
+pub struct InvalidWikiLang(pub String);
+
+impl fmt::Display for InvalidWikiLang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' is not a Wikipedia language code, expected lowercase ASCII letters, like `en`",
+            self.0
+        )
+    }
+}
+
+impl Error for InvalidWikiLang {}
+
+/**
+Base wikipedia url used for constructing the actual url to a particular wiki page, in the given language.
+Is required because internal wiki links would have relative path common to this domain.
+*/
+pub fn base_wiki_url(lang: &str) -> Result<String, InvalidWikiLang> {
+    match !lang.is_empty() && lang.chars().all(|c| c.is_ascii_lowercase()) {
+        true => Ok(format!("https://{lang}.wikipedia.org")),
+        false => Err(InvalidWikiLang(lang.to_string())),
+    }
+}
@@ -0,0 +1,85 @@
+use super::Fetcher;
+use std::error::Error;
+
+/**
+Cache of the wiki domain's robots.txt rules, which is fetched only once on the first lookup.
+Only the `Disallow` path prefixes under `User-agent: *` are considered.
+*/
+#[derive(Default)]
+pub struct RobotsCache {
+    disallowed: Option<Vec<String>>,
+}
+
+impl RobotsCache {
+    pub fn new() -> Self {
+        Self { disallowed: None }
+    }
+
+    /// Checks if the relative path is allowed to be crawled, fetching the robots.txt if required.
+    pub async fn is_allowed(
+        &mut self,
+        fetcher: &Fetcher,
+        path: &str,
+    ) -> Result<bool, Box<dyn Error>> {
+        if self.disallowed.is_none() {
+            if let Some(rate_limiter) = &fetcher.rate_limiter {
+                rate_limiter.wait().await;
+            }
+
+            let response = fetcher
+                .client
+                .get(format!("{}/robots.txt", fetcher.base_url))
+                .send()
+                .await?;
+
+            // A missing robots.txt doesn't restrict anything
+            let disallowed = match response.status().is_success() {
+                true => parse_disallow_rules(&response.text().await?),
+                false => Vec::new(),
+            };
+            self.disallowed = Some(disallowed);
+        }
+
+        Ok(self
+            .disallowed
+            .iter()
+            .flatten()
+            .all(|prefix| !path.starts_with(prefix.as_str())))
+    }
+}
+
+/// Collects the `Disallow` path prefixes from the robots.txt groups applying to every user agent.
+pub fn parse_disallow_rules(robots_txt: &str) -> Vec<String> {
+    let mut disallowed = Vec::new();
+    let mut group_applies = false;
+    let mut in_agent_lines = false;
+
+    for line in robots_txt.lines() {
+        // Comments can start anywhere on a line
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let Some((field, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        match field.trim().to_ascii_lowercase().as_str() {
+            // Consecutive user agent lines share the same group of rules
+            "user-agent" => {
+                if !in_agent_lines {
+                    group_applies = false;
+                }
+                group_applies |= value == "*";
+                in_agent_lines = true;
+            }
+            field => {
+                in_agent_lines = false;
+                // An empty disallow rule allows everything
+                if field == "disallow" && group_applies && !value.is_empty() {
+                    disallowed.push(value.to_string());
+                }
+            }
+        }
+    }
+
+    disallowed
+}
@@ -0,0 +1,95 @@
+use super::{normalize_wiki_ref, Fetcher};
+use scraper::{CaseSensitivity, ElementRef, Html, Node, Selector};
+use std::error::Error;
+
+// Disambiguation pages have a notice box, and are listed under the disambiguation category
+static DISAMBIGUATION_MARKER: &str =
+    "#disambigbox, .dmbox, #catlinks a[href$='/Category:Disambiguation_pages']";
+
+/// Checks if the page is a disambiguation page, which lists the articles sharing its title.
+pub fn is_disambiguation(document: &Html) -> bool {
+    let marker = Selector::parse(DISAMBIGUATION_MARKER).expect("to be a valid selector");
+    document.select(&marker).next().is_some()
+}
+
+/**
+Display title of the page, as the text of its first heading, which can differ from its slug ( eg. for a redirect ).
+Titles with formatting markup ( like the italics of a film title ) are taken as their combined text.
+*/
+pub fn parse_page_title(document: &Html) -> Option<String> {
+    let heading = Selector::parse("h1#firstHeading").expect("to be a valid selector");
+    let title = document
+        .select(&heading)
+        .next()?
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Some(title).filter(|title| !title.is_empty())
+}
+
+/// Plain text of the paragraph, leaving out the citation markers ( [1], [citation needed], etc. ).
+fn paragraph_text(paragraph: ElementRef) -> String {
+    let text = paragraph
+        .descendants()
+        .filter_map(|node| match node.value() {
+            Node::Text(text) => Some((node, text)),
+            _ => None,
+        })
+        .filter(|(node, _)| {
+            node.ancestors()
+                .filter_map(ElementRef::wrap)
+                .all(|element| {
+                    let element = element.value();
+                    element.name() != "sup"
+                        || !element.has_class("reference", CaseSensitivity::AsciiCaseInsensitive)
+                })
+        })
+        .map(|(_, text)| &**text)
+        .collect::<String>();
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/**
+Utility function that is used to parse the summary of the wiki page html document,
+which is the first non-empty paragraph of the article outside of any infobox or table.
+Disambiguation pages don't have a real summary, and so an empty summary is returned for them.
+*/
+pub fn parse_wiki_summary(html: &str) -> Result<String, Box<dyn Error>> {
+    let document = Html::parse_document(html);
+    if is_disambiguation(&document) {
+        return Ok(String::new());
+    }
+
+    let paragraphs = Selector::parse("div#bodyContent p:not(.mw-empty-elt)")?;
+    let summary = document
+        .select(&paragraphs)
+        .filter(|paragraph| {
+            paragraph
+                .ancestors()
+                .filter_map(ElementRef::wrap)
+                .all(|element| element.value().name() != "table")
+        })
+        .map(paragraph_text)
+        .find(|text| !text.is_empty())
+        .unwrap_or_default();
+
+    Ok(summary)
+}
+
+/**
+Utility function that is used to fetch the wiki page html document and extract its summary.
+The argument `url_ref` is the relative path ( excluding the wiki domain ) to the page.
+*/
+pub async fn fetch_wiki_summary(
+    fetcher: &Fetcher,
+    url_ref: &str,
+) -> Result<String, Box<dyn Error>> {
+    let url_ref = normalize_wiki_ref(url_ref)?;
+
+    let page = fetcher.fetch_page(&url_ref).await?;
+    parse_wiki_summary(&page.html)
+}
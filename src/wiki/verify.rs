@@ -0,0 +1,59 @@
+use super::Fetcher;
+use futures::stream::{FuturesUnordered, StreamExt};
+use reqwest::StatusCode;
+use std::collections::BTreeSet;
+use tokio::sync::Semaphore;
+use tracing::{debug, warn};
+
+/**
+Checks that the links resolve, with a HEAD request ( following redirects ) for every distinct link,
+and at most `concurrency` requests at the same time, each spaced out by the rate limiter of the fetcher.
+Returns the dead links, which didn't resolve to a successful response, in a sorted order.
+Links which are rate limited ( 429 ), fail on the server ( 5xx ) or can't be requested at all are only
+warned about, as they can't be told to be dead.
+*/
+pub async fn verify_links<'a>(
+    fetcher: &Fetcher,
+    links: impl IntoIterator<Item = &'a String>,
+    concurrency: usize,
+) -> Vec<String> {
+    let semaphore = Semaphore::new(concurrency);
+    let mut in_flight = links
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|link| {
+            let semaphore = &semaphore;
+            async move {
+                let _permit = semaphore
+                    .acquire()
+                    .await
+                    .expect("Semaphore is never closed");
+                if let Some(rate_limiter) = &fetcher.rate_limiter {
+                    rate_limiter.wait().await;
+                }
+
+                let url = format!("{}{link}", fetcher.base_url);
+                debug!(url, "verifying link");
+                (link, fetcher.client.head(&url).send().await)
+            }
+        })
+        .collect::<FuturesUnordered<_>>();
+
+    let mut dead = BTreeSet::new();
+    while let Some((link, response)) = in_flight.next().await {
+        match response.map(|response| response.status()) {
+            Ok(status) if status.is_success() => {}
+            Ok(status) if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() => {
+                warn!(link, "could not verify link ( {status} )");
+            }
+            Ok(status) => {
+                debug!(link, %status, "found dead link");
+                dead.insert(link.clone());
+            }
+            Err(err) => warn!(link, "could not verify link ( {err} )"),
+        }
+    }
+
+    dead.into_iter().collect()
+}
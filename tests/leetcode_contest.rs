@@ -0,0 +1,33 @@
+use scraprs::leetcode::{parse_contest_html, ContestQuestion, ScrapeError};
+
+static CONTEST_HTML: &str = include_str!("fixtures/contest.html");
+
+#[test]
+fn contest_problems_are_listed_with_their_points() {
+    let questions = parse_contest_html(CONTEST_HTML).expect("fixture to parse");
+
+    assert_eq!(questions.len(), 4);
+    assert_eq!(
+        questions[0],
+        ContestQuestion {
+            slug: "minimum-number-of-chairs-in-a-waiting-room".to_string(),
+            name: "Minimum Number of Chairs in a Waiting Room".to_string(),
+            points: 3,
+        }
+    );
+    assert_eq!(
+        questions
+            .iter()
+            .map(|question| question.points)
+            .collect::<Vec<_>>(),
+        [3, 4, 5, 6]
+    );
+}
+
+#[test]
+fn contest_problems_without_points_fail_to_parse() {
+    let html = CONTEST_HTML.replace("badge", "label");
+    let err = parse_contest_html(&html).expect_err("parsing to fail");
+
+    assert!(matches!(err, ScrapeError::MissingElement(selector) if selector == ".badge"));
+}
@@ -0,0 +1,61 @@
+use scraprs::leetcode::{parse_constraints_html, parse_examples_html};
+
+static DESCRIPTION_HTML: &str = r#"
+    <p>Given an array of integers <code>nums</code>&nbsp;and an integer <code>target</code>, return indices of the two numbers.</p>
+    <p><strong class="example">Example 1:</strong></p>
+    <pre><strong>Input:</strong> nums = [2,7,11,15], target = 9
+<strong>Output:</strong> [0,1]
+<strong>Explanation:</strong> Because nums[0] + nums[1] == 9, we return [0, 1].
+</pre>
+    <p><strong class="example">Example 2:</strong></p>
+    <pre><strong>Input:</strong> nums = [3,2,4], target = 6
+<strong>Output:</strong> [1,2]
+</pre>
+    <p><strong>Constraints:</strong></p>
+    <ul>
+        <li><code>2 &lt;= nums.length &lt;= 10<sup>4</sup></code></li>
+        <li><code>-10<sup>9</sup> &lt;= nums[i] &lt;= 10<sup>9</sup></code></li>
+        <li><strong>Only one valid answer exists.</strong></li>
+    </ul>
+"#;
+
+#[test]
+fn constraints_are_listed_with_powers() {
+    assert_eq!(
+        parse_constraints_html(DESCRIPTION_HTML),
+        [
+            "2 <= nums.length <= 10^4",
+            "-10^9 <= nums[i] <= 10^9",
+            "Only one valid answer exists.",
+        ]
+    );
+}
+
+#[test]
+fn description_without_constraints() {
+    assert!(parse_constraints_html("<p>Just a statement.</p><ul><li>a</li></ul>").is_empty());
+}
+
+#[test]
+fn examples_are_split_into_input_and_output() {
+    let examples = parse_examples_html(DESCRIPTION_HTML);
+
+    assert_eq!(examples.len(), 2);
+    assert_eq!(examples[0].input, "nums = [2,7,11,15], target = 9");
+    assert_eq!(examples[0].output, "[0,1]");
+    assert_eq!(
+        examples[0].explanation.as_deref(),
+        Some("Because nums[0] + nums[1] == 9, we return [0, 1].")
+    );
+    assert_eq!(examples[1].output, "[1,2]");
+    assert_eq!(examples[1].explanation, None);
+}
+
+#[test]
+fn unlabelled_example_is_kept_raw() {
+    let examples = parse_examples_html("<pre>  [[1,2],[3,4]] -> 10  </pre>");
+
+    assert_eq!(examples.len(), 1);
+    assert!(examples[0].input.is_empty());
+    assert_eq!(examples[0].raw.as_deref(), Some("[[1,2],[3,4]] -> 10"));
+}
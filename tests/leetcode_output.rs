@@ -0,0 +1,121 @@
+use chrono::NaiveDate;
+use scraprs::leetcode::{
+    parse_problemset_html, problem_file_name, problems_to_csv, prune_history, slugify, DatedPotd,
+    OutputFormat, RowSelectors, LEETCODE_DOMAIN,
+};
+
+static PROBLEMSET_HTML: &str = include_str!("fixtures/problemset.html");
+
+#[test]
+fn slugifies_names_to_filesystem_safe_words() {
+    assert_eq!(slugify("Two Sum"), "two-sum");
+    assert_eq!(slugify("Pow(x, n)"), "pow-x-n");
+    assert_eq!(slugify("A <Tricky>: Name? *|\\/"), "a-tricky-name");
+}
+
+#[test]
+fn names_problem_files_by_number_and_slug() {
+    let problemset =
+        parse_problemset_html(PROBLEMSET_HTML, &RowSelectors::default(), LEETCODE_DOMAIN)
+            .expect("fixture to parse");
+
+    let two_sum = problemset
+        .problems
+        .iter()
+        .find(|problem| problem.number == 1)
+        .expect("fixture to list the problem");
+    assert_eq!(
+        problem_file_name(two_sum, OutputFormat::Json),
+        "1-two-sum.json"
+    );
+}
+
+#[test]
+fn names_problem_files_by_the_url_slug_rather_than_the_name() {
+    let mut problemset =
+        parse_problemset_html(PROBLEMSET_HTML, &RowSelectors::default(), LEETCODE_DOMAIN)
+            .expect("fixture to parse");
+    let problem = &mut problemset.problems[0];
+    problem.name = "Pow(x, n)".to_string();
+    problem.slug = "powx-n".to_string();
+
+    assert_eq!(
+        problem_file_name(problem, OutputFormat::Toml),
+        format!("{}-powx-n.toml", problem.number)
+    );
+}
+
+#[test]
+fn writes_problems_as_csv_rows_under_a_header() {
+    let problemset =
+        parse_problemset_html(PROBLEMSET_HTML, &RowSelectors::default(), LEETCODE_DOMAIN)
+            .expect("fixture to parse");
+
+    let csv = problems_to_csv(&problemset.problems).expect("problems to serialize");
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("number,name,url,difficulty,acceptance_rate,solution_url,is_premium")
+    );
+    assert_eq!(lines.count(), problemset.problems.len());
+}
+
+#[test]
+fn escapes_csv_fields_with_commas_and_quotes() {
+    let mut problemset =
+        parse_problemset_html(PROBLEMSET_HTML, &RowSelectors::default(), LEETCODE_DOMAIN)
+            .expect("fixture to parse");
+    problemset.problems.truncate(1);
+    problemset.problems[0].name = "Pow(x, n) \"fast\"".to_string();
+
+    let csv = problems_to_csv(&problemset.problems).expect("problems to serialize");
+    assert!(csv.contains(",\"Pow(x, n) \"\"fast\"\"\","));
+}
+
+#[test]
+fn prunes_history_entries_before_the_since_date() {
+    let problemset =
+        parse_problemset_html(PROBLEMSET_HTML, &RowSelectors::default(), LEETCODE_DOMAIN)
+            .expect("fixture to parse");
+    let mut history = ["20240101", "20240115", "not a date"]
+        .into_iter()
+        .zip(problemset.problems)
+        .map(|(date, potd)| DatedPotd {
+            date: date.to_string(),
+            potd,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(history.len(), 3);
+
+    let since = NaiveDate::from_ymd_opt(2024, 1, 15).expect("to be a valid date");
+    assert_eq!(prune_history(&mut history, since, "%Y%m%d"), 1);
+
+    // Entries whose dates don't parse are kept
+    let dates = history
+        .iter()
+        .map(|entry| entry.date.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(dates, ["20240115", "not a date"]);
+}
+
+#[test]
+fn serializes_code_templates_in_language_order() {
+    let mut problemset =
+        parse_problemset_html(PROBLEMSET_HTML, &RowSelectors::default(), LEETCODE_DOMAIN)
+            .expect("fixture to parse");
+    let problem = &mut problemset.problems[0];
+    for lang in ["Python3", "C++", "Rust", "Java"] {
+        problem
+            .code_templates
+            .insert(lang.to_string(), format!("// {lang}"));
+    }
+
+    let json = OutputFormat::Json
+        .serialize(problem, true)
+        .expect("problem to serialize");
+    let positions = ["C++", "Java", "Python3", "Rust"].map(|lang| {
+        json.find(&format!("\"{lang}\""))
+            .expect("template to be serialized")
+    });
+    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+}
@@ -0,0 +1,254 @@
+use scraper::{Html, Selector};
+use scraprs::leetcode::{
+    parse_column_layout_html, parse_count, parse_problem_row_html, parse_problemset_html,
+    problem_slug, ColumnLayout, Difficulty, PotdInfo, Problemset, RowSelectors, ScrapeError,
+    LEETCODE_DOMAIN,
+};
+
+static PROBLEMSET_HTML: &str = include_str!("fixtures/problemset.html");
+static PROBLEM_ROWS_HTML: &str = include_str!("fixtures/problem_rows.html");
+
+fn problemset() -> Problemset {
+    parse_problemset_html(PROBLEMSET_HTML, &RowSelectors::default(), LEETCODE_DOMAIN)
+        .expect("fixture to parse")
+}
+
+fn problem(problemset: &Problemset, number: u32) -> &PotdInfo {
+    problemset
+        .problems
+        .iter()
+        .find(|problem| problem.number == number)
+        .expect("fixture to list the problem")
+}
+
+#[test]
+fn potd_row_is_kept_apart() {
+    let problemset = problemset();
+    let potd = problemset.potd.as_ref().expect("fixture to have a POTD");
+
+    assert_eq!(potd.number, 64);
+    assert_eq!(potd.name, "Minimum Path Sum");
+    assert_eq!(potd.url, "https://leetcode.com/problems/minimum-path-sum/");
+    assert_eq!(potd.slug, "minimum-path-sum");
+    assert!(matches!(potd.difficulty, Difficulty::Medium));
+    assert!((potd.acceptance_rate - 0.624).abs() < 1e-6);
+    assert!(!potd.is_premium);
+    assert_eq!(
+        potd.solution_url.as_deref(),
+        Some("https://leetcode.com/problems/minimum-path-sum/solution")
+    );
+
+    assert!(problemset
+        .problems
+        .iter()
+        .all(|problem| problem.number != 64));
+}
+
+#[test]
+fn header_row_is_skipped() {
+    let numbers = problemset()
+        .problems
+        .iter()
+        .map(|problem| problem.number)
+        .collect::<Vec<_>>();
+
+    assert_eq!(numbers, [1, 4, 157]);
+}
+
+#[test]
+fn problem_without_solution() {
+    let problemset = problemset();
+    let median = problem(&problemset, 4);
+
+    assert_eq!(median.name, "Median of Two Sorted Arrays");
+    assert!(matches!(median.difficulty, Difficulty::Hard));
+    assert!(!median.is_premium);
+    assert_eq!(median.solution_url, None);
+}
+
+#[test]
+fn premium_problem_has_no_solution_url() {
+    let problemset = problemset();
+    let read4 = problem(&problemset, 157);
+
+    assert!(read4.is_premium);
+    assert!(matches!(read4.difficulty, Difficulty::Easy));
+    assert_eq!(read4.solution_url, None);
+}
+
+/// Problem rows of the rows fixture, parsed on their own rather than as a problems table.
+fn problem_rows() -> Vec<PotdInfo> {
+    let html = Html::parse_fragment(PROBLEM_ROWS_HTML);
+    let row = Selector::parse("div[role='row']").expect("to be a valid selector");
+    html.select(&row)
+        .map(|row| {
+            parse_problem_row_html(
+                row,
+                &ColumnLayout::default(),
+                &RowSelectors::default(),
+                LEETCODE_DOMAIN,
+            )
+            .expect("fixture row to parse")
+        })
+        .collect()
+}
+
+#[test]
+fn premium_badge_row_keeps_its_url_but_skips_the_solution() {
+    let rows = problem_rows();
+    let meeting_rooms = &rows[0];
+
+    assert!(meeting_rooms.is_premium);
+    assert_eq!(meeting_rooms.number, 253);
+    assert_eq!(
+        meeting_rooms.url,
+        "https://leetcode.com/problems/meeting-rooms-ii/"
+    );
+    assert_eq!(meeting_rooms.solution_url, None);
+}
+
+#[test]
+fn free_row_keeps_its_solution_url() {
+    let rows = problem_rows();
+    let parentheses = &rows[1];
+
+    assert!(!parentheses.is_premium);
+    assert_eq!(
+        parentheses.solution_url.as_deref(),
+        Some("https://leetcode.com/problems/valid-parentheses/solution")
+    );
+}
+
+#[test]
+fn unexpected_difficulty_is_an_error() {
+    let html = PROBLEMSET_HTML.replace(">Hard<", ">Impossible<");
+
+    let err = parse_problemset_html(&html, &RowSelectors::default(), LEETCODE_DOMAIN)
+        .err()
+        .expect("parsing to fail");
+    assert_eq!(
+        err.to_string(),
+        "unexpected problem difficulty 'Impossible'"
+    );
+}
+
+#[test]
+fn column_layout_is_read_off_the_header() {
+    let html = r#"
+        <div role="table">
+          <div role="row">
+            <div role="columnheader">Status</div>
+            <div role="columnheader">Difficulty</div>
+            <div role="columnheader">Title</div>
+            <div role="columnheader">Acceptance</div>
+            <div role="columnheader">Solution</div>
+          </div>
+          <div role="row">
+            <div role="cell"></div>
+            <div role="cell"><span>Easy</span></div>
+            <div role="cell"><a href="/problems/two-sum/">1. Two Sum</a></div>
+            <div role="cell"><span>49.7%</span></div>
+            <div role="cell"><a aria-label="solution" href="/problems/two-sum/solution">Solution</a></div>
+          </div>
+        </div>
+    "#;
+    let problemset = parse_problemset_html(html, &RowSelectors::default(), LEETCODE_DOMAIN)
+        .expect("reordered table to parse");
+    let two_sum = problem(&problemset, 1);
+
+    assert_eq!(two_sum.name, "Two Sum");
+    assert!(matches!(two_sum.difficulty, Difficulty::Easy));
+    assert!((two_sum.acceptance_rate - 0.497).abs() < 1e-6);
+    assert_eq!(
+        two_sum.solution_url.as_deref(),
+        Some("https://leetcode.com/problems/two-sum/solution")
+    );
+}
+
+#[test]
+fn column_layout_falls_back_without_a_header() {
+    let html = PROBLEMSET_HTML.replace("columnheader", "presentation");
+    let document = Html::parse_document(&html);
+
+    assert_eq!(
+        parse_column_layout_html(document.root_element()),
+        ColumnLayout::default()
+    );
+    assert_eq!(
+        parse_column_layout_html(Html::parse_document(PROBLEMSET_HTML).root_element()),
+        ColumnLayout::default()
+    );
+}
+
+#[test]
+fn resolves_problem_links_on_the_domain() {
+    let problemset = parse_problemset_html(
+        PROBLEMSET_HTML,
+        &RowSelectors::default(),
+        "https://leetcode.cn/",
+    )
+    .expect("fixture to parse");
+    let potd = problemset.potd.expect("fixture to have a POTD");
+
+    assert_eq!(potd.url, "https://leetcode.cn/problems/minimum-path-sum/");
+    assert_eq!(
+        potd.solution_url.as_deref(),
+        Some("https://leetcode.cn/problems/minimum-path-sum/solution")
+    );
+}
+
+#[test]
+fn slugs_are_read_off_the_problem_path() {
+    assert_eq!(
+        problem_slug("https://leetcode.com/problems/two-sum/description/?envType=daily"),
+        Some("two-sum")
+    );
+    assert_eq!(problem_slug("/problems/two-sum"), Some("two-sum"));
+    assert_eq!(problem_slug("https://leetcode.com/problemset/all/"), None);
+    assert_eq!(problem_slug("https://leetcode.com/problems/"), None);
+}
+
+#[test]
+fn abbreviated_counts_are_scaled() {
+    assert_eq!(parse_count("987").ok(), Some(987));
+    assert_eq!(parse_count("12.3K").ok(), Some(12_300));
+    assert_eq!(parse_count("1,204").ok(), Some(1_204));
+    assert_eq!(parse_count("1.2M").ok(), Some(1_200_000));
+    assert_eq!(parse_count("4.2B").ok(), Some(4_200_000_000));
+}
+
+#[test]
+fn counts_out_of_range_are_an_error() {
+    assert!(matches!(parse_count("4.3B"), Err(ScrapeError::ParseNumber(count)) if count == "4.3B"));
+    assert!(matches!(
+        parse_count("5B"),
+        Err(ScrapeError::ParseNumber(_))
+    ));
+    assert!(matches!(
+        parse_count("-1"),
+        Err(ScrapeError::ParseNumber(_))
+    ));
+}
+
+#[test]
+fn row_selectors_can_be_overridden() {
+    let selectors = RowSelectors::from_toml("difficulty_label = \"span.difficulty\"")
+        .expect("overrides to parse");
+    assert_eq!(selectors.difficulty_label, "span.difficulty");
+    assert_eq!(
+        selectors.problem_anchor,
+        RowSelectors::default().problem_anchor
+    );
+
+    // Rows whose difficulty label doesn't match the override can't be parsed
+    let err = parse_problemset_html(PROBLEMSET_HTML, &selectors, LEETCODE_DOMAIN)
+        .err()
+        .expect("parsing to fail");
+    assert!(err.to_string().contains("span.difficulty"));
+}
+
+#[test]
+fn invalid_row_selectors_are_rejected() {
+    assert!(RowSelectors::from_toml("solution_anchor = \"a[\"").is_err());
+    assert!(RowSelectors::from_toml("title_anchor = \"a\"").is_err());
+}
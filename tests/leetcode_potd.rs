@@ -0,0 +1,285 @@
+use scraper::{ElementRef, Html, Selector};
+use scraprs::leetcode::{
+    scrape_all, scrape_potd, Difficulty, PotdInfo, ScrapeDriver, ScrapeElement, ScrapeError,
+    ScrapeOptions,
+};
+use std::{collections::HashMap, time::Duration};
+
+static HEADER_ROW: &str = r#"<div role="row">
+    <div role="columnheader">Status</div>
+    <div role="columnheader">Title</div>
+    <div role="columnheader">Solution</div>
+    <div role="columnheader">Acceptance</div>
+    <div role="columnheader">Difficulty</div>
+</div>"#;
+static POTD_ROW: &str = r#"<div role="row">
+    <div role="cell"><a href="/problems/minimum-path-sum/?envType=daily-question"><svg></svg></a></div>
+    <div role="cell"><a href="/problems/minimum-path-sum/">64. Minimum Path Sum</a></div>
+    <div role="cell"><a aria-label="solution" href="/problems/minimum-path-sum/solution"></a></div>
+    <div role="cell"><span>62.4%</span></div>
+    <div role="cell"><span>Medium</span></div>
+</div>"#;
+static PROBLEM_ROW: &str = r#"<div role="row">
+    <div role="cell"></div>
+    <div role="cell"><a href="/problems/two-sum/">1. Two Sum</a></div>
+    <div role="cell"></div>
+    <div role="cell"><span>49.1%</span></div>
+    <div role="cell"><span>Easy</span></div>
+</div>"#;
+
+/// Canned element, queried with the html parser rather than a browser.
+struct MockElement {
+    html: String,
+}
+
+impl MockElement {
+    fn new(html: impl Into<String>) -> Self {
+        Self { html: html.into() }
+    }
+
+    /// Elements at the top of the html, as the html of a query result is their concatenation.
+    fn split(html: &str) -> Vec<Self> {
+        Html::parse_fragment(html)
+            .root_element()
+            .children()
+            .filter_map(ElementRef::wrap)
+            .map(|element| Self::new(element.html()))
+            .collect()
+    }
+
+    fn select(&self, selector: &str) -> Result<Vec<Self>, ScrapeError> {
+        let selector = Selector::parse(selector)
+            .map_err(|_| ScrapeError::MissingElement("selector".into()))?;
+        let fragment = Html::parse_fragment(&self.html);
+        Ok(fragment
+            .select(&selector)
+            .map(|element| Self::new(element.html()))
+            .collect())
+    }
+}
+
+impl ScrapeElement for MockElement {
+    async fn find(&self, selector: &str) -> Result<Self, ScrapeError> {
+        // Elements select their descendants, and so the element itself is left out
+        self.select(selector)?
+            .into_iter()
+            .find(|element| element.html != self.html)
+            .ok_or(ScrapeError::MissingElement("mock element".into()))
+    }
+
+    async fn query(&self, selector: &str) -> Result<Vec<Self>, ScrapeError> {
+        Ok(self
+            .select(selector)?
+            .into_iter()
+            .filter(|element| element.html != self.html)
+            .collect())
+    }
+
+    async fn attr(&self, name: &str) -> Result<Option<String>, ScrapeError> {
+        let fragment = Html::parse_fragment(&self.html);
+        let element = fragment
+            .root_element()
+            .children()
+            .find_map(ElementRef::wrap)
+            .ok_or(ScrapeError::MissingElement("mock element".into()))?;
+        Ok(element.value().attr(name).map(str::to_string))
+    }
+
+    async fn inner_html(&self) -> Result<String, ScrapeError> {
+        let fragment = Html::parse_fragment(&self.html);
+        let element = fragment
+            .root_element()
+            .children()
+            .find_map(ElementRef::wrap)
+            .ok_or(ScrapeError::MissingElement("mock element".into()))?;
+        Ok(element.inner_html())
+    }
+
+    async fn outer_html(&self) -> Result<String, ScrapeError> {
+        Ok(self.html.clone())
+    }
+
+    async fn text(&self) -> Result<String, ScrapeError> {
+        Ok(Html::parse_fragment(&self.html)
+            .root_element()
+            .text()
+            .collect())
+    }
+
+    async fn click(&self) -> Result<(), ScrapeError> {
+        Ok(())
+    }
+}
+
+/// Canned page, holding the html of the elements found on it by their selector, concatenated for queries.
+struct MockDriver {
+    elements: HashMap<&'static str, String>,
+}
+
+impl MockDriver {
+    fn with_rows(rows: &[&str]) -> Self {
+        let table = format!(
+            r#"<div class="relative"><div role="table"><div role="rowgroup">{}</div></div></div>"#,
+            rows.concat()
+        );
+        // Table is already active, and so is only found by the fallback selector
+        Self {
+            elements: HashMap::from([
+                ("body", r#"<div class="bg-layer-bg"></div>"#.to_string()),
+                ("div:has(div[role='table'])", table),
+            ]),
+        }
+    }
+
+    /// Canned page of the problemset, paginated with a button for every page.
+    fn with_pages(rows: &[&str], pages: u32) -> Self {
+        let mut driver = Self::with_rows(rows);
+        let buttons = (1..=pages)
+            .map(|page| format!("<button>{page}</button>"))
+            .collect::<String>();
+        driver
+            .elements
+            .insert("nav[role='navigation'] button", buttons);
+        driver.elements.insert(
+            "nav[role='navigation'] button[aria-label='next']",
+            r#"<button aria-label="next"></button>"#.to_string(),
+        );
+        driver
+    }
+}
+
+impl ScrapeDriver for MockDriver {
+    type Element = MockElement;
+
+    async fn goto(&self, _url: &str) -> Result<(), ScrapeError> {
+        Ok(())
+    }
+
+    async fn find(&self, selector: &str) -> Result<MockElement, ScrapeError> {
+        self.elements
+            .get(selector)
+            .map(MockElement::new)
+            .ok_or(ScrapeError::MissingElement("mock element".into()))
+    }
+
+    async fn query(&self, selector: &str) -> Result<Vec<MockElement>, ScrapeError> {
+        Ok(self
+            .elements
+            .get(selector)
+            .map_or_else(Vec::new, |html| MockElement::split(html)))
+    }
+
+    async fn scrape_problem_details(
+        &self,
+        _options: &ScrapeOptions,
+        _problem: &mut PotdInfo,
+    ) -> Result<(), ScrapeError> {
+        Ok(())
+    }
+}
+
+fn options() -> ScrapeOptions {
+    ScrapeOptions {
+        timeout: Some(Duration::from_millis(50)),
+        poll_interval: Some(Duration::from_millis(5)),
+        ..ScrapeOptions::default()
+    }
+}
+
+#[tokio::test]
+async fn scrapes_the_potd_row_of_the_problems_table() {
+    let driver = MockDriver::with_rows(&[POTD_ROW, PROBLEM_ROW]);
+    let potd = scrape_potd(&driver, &options())
+        .await
+        .expect("mock page to scrape");
+
+    assert_eq!(potd.number, 64);
+    assert_eq!(potd.slug, "minimum-path-sum");
+    assert!(matches!(potd.difficulty, Difficulty::Medium));
+    assert_eq!(
+        potd.solution_url.as_deref(),
+        Some("https://leetcode.com/problems/minimum-path-sum/solution")
+    );
+}
+
+#[tokio::test]
+async fn times_out_when_the_potd_never_loads() {
+    let driver = MockDriver::with_rows(&[HEADER_ROW, PROBLEM_ROW]);
+    let res = scrape_potd(&driver, &options()).await;
+
+    assert!(matches!(res, Err(ScrapeError::WebDriver(_))));
+}
+
+#[tokio::test]
+async fn fails_on_a_title_without_the_problem_number() {
+    let row = POTD_ROW.replace("64. Minimum Path Sum", "Minimum Path Sum");
+    let driver = MockDriver::with_rows(&[&row, PROBLEM_ROW]);
+    let res = scrape_potd(&driver, &options()).await;
+
+    assert!(
+        matches!(res, Err(ScrapeError::UnexpectedNameFormat(name)) if name == "Minimum Path Sum")
+    );
+}
+
+#[tokio::test]
+async fn fails_on_a_title_anchor_without_a_link() {
+    let row = POTD_ROW.replace(r#"<a href="/problems/minimum-path-sum/">"#, "<a>");
+    let driver = MockDriver::with_rows(&[&row, PROBLEM_ROW]);
+    let res = scrape_potd(&driver, &options()).await;
+
+    assert!(matches!(res, Err(ScrapeError::MissingAttribute("href"))));
+}
+
+#[tokio::test]
+async fn fails_on_an_unknown_difficulty() {
+    let row = POTD_ROW.replace("Medium", "Impossible");
+    let driver = MockDriver::with_rows(&[&row, PROBLEM_ROW]);
+    let res = scrape_potd(&driver, &options()).await;
+
+    assert!(matches!(res, Err(ScrapeError::UnexpectedDifficulty(text)) if text == "Impossible"));
+}
+
+#[tokio::test]
+async fn fails_on_a_malformed_acceptance_rate() {
+    let row = POTD_ROW.replace("62.4%", "n/a");
+    let driver = MockDriver::with_rows(&[&row, PROBLEM_ROW]);
+    let res = scrape_potd(&driver, &options()).await;
+
+    assert!(matches!(res, Err(ScrapeError::ParseRate(rate)) if rate == "n/a"));
+}
+
+#[tokio::test]
+async fn scrapes_the_problemset_without_the_pinned_potd() {
+    let driver = MockDriver::with_pages(&[POTD_ROW, PROBLEM_ROW], 1);
+    let problems = scrape_all(&driver, &options())
+        .await
+        .expect("mock page to scrape");
+
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].number, 1);
+    assert_eq!(problems[0].slug, "two-sum");
+    assert!(matches!(problems[0].difficulty, Difficulty::Easy));
+}
+
+#[tokio::test]
+async fn scrapes_only_up_to_the_max_pages_of_the_problemset() {
+    let driver = MockDriver::with_pages(&[POTD_ROW, PROBLEM_ROW], 3);
+    let options = ScrapeOptions {
+        max_pages: Some(1),
+        ..options()
+    };
+    let problems = scrape_all(&driver, &options)
+        .await
+        .expect("mock page to scrape");
+
+    assert_eq!(problems.len(), 1);
+}
+
+#[tokio::test]
+async fn times_out_when_the_next_problemset_page_never_loads() {
+    // Next page button is clicked, but the canned table keeps the rows of the first page
+    let driver = MockDriver::with_pages(&[POTD_ROW, PROBLEM_ROW], 2);
+    let res = scrape_all(&driver, &options()).await;
+
+    assert!(matches!(res, Err(ScrapeError::WebDriver(_))));
+}
@@ -0,0 +1,44 @@
+use scraprs::leetcode::SessionCookies;
+
+#[test]
+fn parses_a_cookie_header() {
+    let cookies = "LEETCODE_SESSION=secret-session; csrftoken=secret-token;"
+        .parse::<SessionCookies>()
+        .expect("cookies to parse");
+
+    assert_eq!(
+        cookies.names().collect::<Vec<_>>(),
+        ["LEETCODE_SESSION", "csrftoken"]
+    );
+}
+
+#[test]
+fn parses_newline_separated_cookies() {
+    let cookies = "csrftoken=secret-token\nLEETCODE_SESSION=secret-session\n"
+        .parse::<SessionCookies>()
+        .expect("cookies to parse");
+
+    assert_eq!(cookies.names().count(), 2);
+}
+
+#[test]
+fn cookie_values_are_never_shown() {
+    let cookies = "LEETCODE_SESSION=secret-session; csrftoken=secret-token"
+        .parse::<SessionCookies>()
+        .expect("cookies to parse");
+
+    let debug = format!("{cookies:?}");
+    assert!(debug.contains("LEETCODE_SESSION"));
+    assert!(!debug.contains("secret"));
+
+    let err = "LEETCODE_SESSION=secret-session; csrftoken="
+        .parse::<SessionCookies>()
+        .expect_err("empty cookie to fail");
+    assert!(err.contains("csrftoken") && !err.contains("secret"));
+}
+
+#[test]
+fn requires_the_session_cookie() {
+    assert!("csrftoken=secret-token".parse::<SessionCookies>().is_err());
+    assert!("not a cookie".parse::<SessionCookies>().is_err());
+}
@@ -0,0 +1,48 @@
+use rusqlite::Connection;
+use scraprs::leetcode::{parse_problemset_html, upsert_problems, RowSelectors, LEETCODE_DOMAIN};
+
+static PROBLEMSET_HTML: &str = include_str!("fixtures/problemset.html");
+
+#[test]
+fn problems_are_upserted_by_their_number() {
+    let path = std::env::temp_dir().join(format!("scraprs-problems-{}.db", std::process::id()));
+    let mut problems =
+        parse_problemset_html(PROBLEMSET_HTML, &RowSelectors::default(), LEETCODE_DOMAIN)
+            .expect("fixture to parse")
+            .problems;
+
+    let upserted = upsert_problems(&path, "20240101", &problems).expect("problems to be upserted");
+    assert_eq!(upserted, problems.len());
+
+    // Scraping the problems again updates them in place
+    problems[0].acceptance_rate = 0.5;
+    upsert_problems(&path, "20240102", &problems).expect("problems to be upserted again");
+
+    let connection = Connection::open(&path).expect("database to open");
+    let rows = connection
+        .query_row("SELECT COUNT(*) FROM problems", [], |row| {
+            row.get::<_, i64>(0)
+        })
+        .expect("problems to be counted");
+    assert_eq!(rows as usize, problems.len());
+
+    let (rate, first_scraped, last_scraped) = connection
+        .query_row(
+            "SELECT acceptance_rate, first_scraped, last_scraped FROM problems WHERE number = ?1",
+            [problems[0].number],
+            |row| {
+                Ok((
+                    row.get::<_, f64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            },
+        )
+        .expect("problem to be stored");
+    assert!((rate - 0.5).abs() < 1e-6);
+    assert_eq!(first_scraped, "20240101");
+    assert_eq!(last_scraped, "20240102");
+
+    drop(connection);
+    std::fs::remove_file(path).expect("database to be removed");
+}
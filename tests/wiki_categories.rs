@@ -0,0 +1,24 @@
+use scraprs::wiki::fetch_categories;
+
+static ARTICLE_HTML: &str = include_str!("fixtures/wiki_article.html");
+
+#[test]
+fn categories_leave_out_hidden_ones() {
+    let categories = fetch_categories(ARTICLE_HTML, false);
+
+    assert_eq!(categories, ["Programming languages", "Mozilla"]);
+}
+
+#[test]
+fn hidden_categories_are_included_on_request() {
+    let categories = fetch_categories(ARTICLE_HTML, true);
+
+    assert_eq!(
+        categories,
+        [
+            "Programming languages",
+            "Mozilla",
+            "Articles with short description"
+        ]
+    );
+}
@@ -0,0 +1,90 @@
+use scraprs::wiki::{
+    build_client, crawl, CrawlOptions, CrawlState, CrawledPage, Fetcher, PoolOptions,
+    DEFAULT_REQUEST_TIMEOUT, DEFAULT_USER_AGENT,
+};
+use std::{collections::VecDeque, sync::atomic::AtomicBool};
+
+fn fetcher() -> Fetcher {
+    Fetcher {
+        client: build_client(
+            DEFAULT_USER_AGENT,
+            None,
+            DEFAULT_REQUEST_TIMEOUT,
+            &PoolOptions::default(),
+        )
+        .expect("client to build"),
+        base_url: "https://en.wikipedia.org".to_string(),
+        cache: None,
+        rate_limiter: None,
+        timeout_retries: 0,
+        max_retries: 0,
+    }
+}
+
+// Crawl of the start page which was already fetched, and so has nothing left queued
+fn finished_state() -> CrawlState {
+    let mut state = CrawlState::new("/wiki/Rust");
+    state.queue = VecDeque::new();
+    state.visited.insert("/wiki/Go".to_string());
+    state.pages.push(CrawledPage {
+        page: "/wiki/Rust".to_string(),
+        title: Some("Rust".to_string()),
+        depth: 0,
+        links: vec![
+            "/wiki/Go".to_string(),
+            "/wiki/Rust".to_string(),
+            "/wiki/Go".to_string(),
+        ],
+        categories: Vec::new(),
+        hidden_categories: Vec::new(),
+        meta: None,
+    });
+    state
+}
+
+#[tokio::test]
+async fn checkpoints_are_written_atomically_and_reloaded() {
+    let path = std::env::temp_dir().join(format!("scraprs-crawl-{}.json", std::process::id()));
+    finished_state()
+        .save(&path)
+        .await
+        .expect("state to be checkpointed");
+
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    assert!(!std::path::Path::new(&temp_path).exists());
+
+    let state = CrawlState::load(&path).await.expect("state to be loaded");
+    assert_eq!(state.start, "/wiki/Rust");
+    assert!(state.visited.contains("/wiki/Go"));
+    assert!(state.queue.is_empty());
+    assert_eq!(state.pages[0].links.len(), 3);
+
+    std::fs::remove_file(path).expect("state file to be removed");
+}
+
+#[tokio::test]
+async fn resumed_crawls_keep_the_checkpointed_pages() {
+    let options = CrawlOptions {
+        depth: 2,
+        resume: Some(finished_state()),
+        ..CrawlOptions::default()
+    };
+    let pages = crawl(&fetcher(), "/wiki/Rust", options, &AtomicBool::new(false))
+        .await
+        .expect("crawl to resume");
+
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0].links, ["/wiki/Go"]);
+}
+
+#[tokio::test]
+async fn checkpoints_of_other_crawls_are_rejected() {
+    let options = CrawlOptions {
+        resume: Some(finished_state()),
+        ..CrawlOptions::default()
+    };
+    let result = crawl(&fetcher(), "/wiki/Go", options, &AtomicBool::new(false)).await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,28 @@
+use scraper::Html;
+use scraprs::wiki::{is_disambiguation, parse_wiki_summary};
+
+static ARTICLE_HTML: &str = include_str!("fixtures/wiki_article.html");
+static DISAMBIGUATION_HTML: &str = include_str!("fixtures/wiki_disambiguation.html");
+
+#[test]
+fn disambiguation_page_is_detected() {
+    assert!(is_disambiguation(&Html::parse_document(
+        DISAMBIGUATION_HTML
+    )));
+    assert!(!is_disambiguation(&Html::parse_document(ARTICLE_HTML)));
+}
+
+#[test]
+fn disambiguation_is_detected_from_the_category_alone() {
+    let html = r#"<div id="bodyContent"><p>Rust may refer to:</p>
+        <div id="catlinks"><a href="/wiki/Category:Disambiguation_pages">Disambiguation pages</a></div>
+    </div>"#;
+
+    assert!(is_disambiguation(&Html::parse_document(html)));
+}
+
+#[test]
+fn disambiguation_page_has_an_empty_summary() {
+    let summary = parse_wiki_summary(DISAMBIGUATION_HTML).expect("fixture to parse");
+    assert!(summary.is_empty());
+}
@@ -0,0 +1,175 @@
+use scraprs::wiki::{
+    build_client, fetch_wiki_hrefs, fetch_wiki_links, parse_retry_after, verify_links, CachedPage,
+    Fetcher, LinkFilter, PageCache, PoolOptions, DEFAULT_REQUEST_TIMEOUT, DEFAULT_USER_AGENT,
+};
+use std::time::Duration;
+
+static ARTICLE_HTML: &str = include_str!("fixtures/wiki_article.html");
+
+#[test]
+fn parses_retry_after_seconds() {
+    assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+    assert_eq!(parse_retry_after(" 0 "), Some(Duration::ZERO));
+}
+
+#[test]
+fn parses_retry_after_dates() {
+    // Dates in the past are no wait at all
+    assert_eq!(
+        parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+        Some(Duration::ZERO)
+    );
+    assert_eq!(parse_retry_after("soon"), None);
+}
+
+#[tokio::test]
+async fn records_the_metadata_of_cached_pages() {
+    let cache = PageCache {
+        dir: std::env::temp_dir().join(format!("scraprs-fetch-{}", std::process::id())),
+        ttl: Duration::from_secs(60),
+    };
+    let base_url = "https://en.wikipedia.org";
+    let url = format!("{base_url}/wiki/Rust_(programming_language)");
+    let page = CachedPage {
+        final_url: url.clone(),
+        html: ARTICLE_HTML.to_string(),
+    };
+    cache.put(&url, &page).await.expect("page to be cached");
+
+    let fetcher = Fetcher {
+        client: build_client(
+            DEFAULT_USER_AGENT,
+            None,
+            DEFAULT_REQUEST_TIMEOUT,
+            &PoolOptions::default(),
+        )
+        .expect("client to build"),
+        base_url: base_url.to_string(),
+        cache: Some(cache),
+        rate_limiter: None,
+        timeout_retries: 0,
+        max_retries: 0,
+    };
+    let page = fetcher
+        .fetch_page("/wiki/Rust_(programming_language)")
+        .await
+        .expect("page to be fetched from the cache");
+
+    assert!(page.meta.cached);
+    assert_eq!(page.meta.status, None);
+    assert_eq!(page.meta.final_url, url);
+    assert_eq!(page.meta.content_length, ARTICLE_HTML.len());
+
+    let dir = fetcher.cache.expect("cache to be kept").dir;
+    std::fs::remove_dir_all(dir).expect("cache dir to be removed");
+}
+
+#[tokio::test]
+async fn cached_pages_resolve_to_their_redirected_path() {
+    let cache = PageCache {
+        dir: std::env::temp_dir().join(format!("scraprs-redirect-{}", std::process::id())),
+        ttl: Duration::from_secs(60),
+    };
+    let base_url = "https://en.wikipedia.org";
+    let page = CachedPage {
+        final_url: format!("{base_url}/wiki/Rust_(programming_language)"),
+        html: ARTICLE_HTML.to_string(),
+    };
+    cache
+        .put(&format!("{base_url}/wiki/Rust_programming_language"), &page)
+        .await
+        .expect("page to be cached");
+
+    let fetcher = Fetcher {
+        client: build_client(
+            DEFAULT_USER_AGENT,
+            None,
+            DEFAULT_REQUEST_TIMEOUT,
+            &PoolOptions::default(),
+        )
+        .expect("client to build"),
+        base_url: base_url.to_string(),
+        cache: Some(cache),
+        rate_limiter: None,
+        timeout_retries: 0,
+        max_retries: 0,
+    };
+    let page = fetcher
+        .fetch_page("/wiki/Rust_programming_language")
+        .await
+        .expect("page to be fetched from the cache");
+
+    // Cached crawls have the same graph nodes as the live ones
+    assert_eq!(page.url_ref, "/wiki/Rust_(programming_language)");
+
+    let dir = fetcher.cache.expect("cache to be kept").dir;
+    std::fs::remove_dir_all(dir).expect("cache dir to be removed");
+}
+
+#[tokio::test]
+async fn fetches_only_the_hrefs_of_the_links() {
+    let cache = PageCache {
+        dir: std::env::temp_dir().join(format!("scraprs-hrefs-{}", std::process::id())),
+        ttl: Duration::from_secs(60),
+    };
+    let base_url = "https://en.wikipedia.org";
+    let url = format!("{base_url}/wiki/Rust_(programming_language)");
+    let page = CachedPage {
+        final_url: url.clone(),
+        html: ARTICLE_HTML.to_string(),
+    };
+    cache.put(&url, &page).await.expect("page to be cached");
+
+    let fetcher = Fetcher {
+        client: build_client(
+            DEFAULT_USER_AGENT,
+            None,
+            DEFAULT_REQUEST_TIMEOUT,
+            &PoolOptions::default(),
+        )
+        .expect("client to build"),
+        base_url: base_url.to_string(),
+        cache: Some(cache),
+        rate_limiter: None,
+        timeout_retries: 0,
+        max_retries: 0,
+    };
+    let url_ref = "/wiki/Rust_(programming_language)";
+    let hrefs = fetch_wiki_hrefs(&fetcher, url_ref, LinkFilter::default())
+        .await
+        .expect("hrefs to be fetched from the cache");
+    let links = fetch_wiki_links(&fetcher, url_ref, LinkFilter::default())
+        .await
+        .expect("links to be fetched from the cache");
+
+    assert!(hrefs.contains(&"/wiki/Mozilla".to_string()));
+    assert_eq!(
+        hrefs,
+        links.into_iter().map(|link| link.href).collect::<Vec<_>>()
+    );
+
+    let dir = fetcher.cache.expect("cache to be kept").dir;
+    std::fs::remove_dir_all(dir).expect("cache dir to be removed");
+}
+
+#[tokio::test]
+async fn unreachable_links_are_not_reported_dead() {
+    // Nothing listens on the discard port, and so every request fails to connect
+    let fetcher = Fetcher {
+        client: build_client(
+            DEFAULT_USER_AGENT,
+            None,
+            DEFAULT_REQUEST_TIMEOUT,
+            &PoolOptions::default(),
+        )
+        .expect("client to build"),
+        base_url: "http://127.0.0.1:9".to_string(),
+        cache: None,
+        rate_limiter: None,
+        timeout_retries: 0,
+        max_retries: 0,
+    };
+    let links = ["/wiki/Rust".to_string(), "/wiki/Go".to_string()];
+
+    assert!(verify_links(&fetcher, &links, 2).await.is_empty());
+}
@@ -0,0 +1,120 @@
+use scraprs::wiki::{degree_stats, normalize_graph, pagerank, to_edgelist, to_graphml, to_json};
+use std::collections::HashMap;
+
+#[test]
+fn graphml_lists_every_node_once_with_its_title() {
+    let graph = HashMap::from([
+        (
+            "/wiki/Rust".to_string(),
+            vec![
+                "/wiki/C%2B%2B".to_string(),
+                "/wiki/Tom_%26_Jerry".to_string(),
+            ],
+        ),
+        ("/wiki/C%2B%2B".to_string(), vec!["/wiki/Rust".to_string()]),
+    ]);
+    let graphml = to_graphml(&graph);
+
+    assert_eq!(graphml.matches("<node ").count(), 3);
+    assert_eq!(graphml.matches("<edge ").count(), 3);
+    assert!(graphml.contains(r#"<node id="/wiki/Rust"><data key="title">Rust</data></node>"#));
+    assert!(graphml.contains(r#"<edge source="/wiki/C%2B%2B" target="/wiki/Rust"/>"#));
+    assert!(graphml.contains(r#"edgedefault="directed""#));
+}
+
+#[test]
+fn json_is_sorted_by_page() {
+    let graph = HashMap::from([
+        ("/wiki/Zig".to_string(), vec!["/wiki/C".to_string()]),
+        (
+            "/wiki/Ada".to_string(),
+            vec!["/wiki/Pascal".to_string(), "/wiki/C".to_string()],
+        ),
+    ]);
+    let json = to_json(&graph);
+
+    assert!(json.find("/wiki/Ada").unwrap() < json.find("/wiki/Zig").unwrap());
+    assert!(json.find("/wiki/Pascal").unwrap() < json.find("\"/wiki/C\"").unwrap());
+    assert_eq!(
+        serde_json::from_str::<HashMap<String, Vec<String>>>(&json).unwrap(),
+        graph
+    );
+}
+
+#[test]
+fn normalized_graph_has_no_duplicate_edges_or_self_loops() {
+    let page = |name: &str| format!("/wiki/{name}");
+    let graph = HashMap::from([
+        (
+            page("A"),
+            vec![page("B"), page("A"), page("C"), page("B"), page("C")],
+        ),
+        (page("B"), vec![page("B"), page("B")]),
+        (page("C"), vec![page("A")]),
+    ]);
+    let graph = normalize_graph(graph);
+
+    assert_eq!(graph.len(), 3);
+    assert_eq!(graph[&page("A")], vec![page("B"), page("C")]);
+    assert!(graph[&page("B")].is_empty());
+    assert_eq!(graph[&page("C")], vec![page("A")]);
+}
+
+#[test]
+fn edgelist_is_deduped_and_sorted() {
+    let graph = HashMap::from([
+        (
+            "/wiki/Zig".to_string(),
+            vec!["/wiki/C".to_string(), "/wiki/C".to_string()],
+        ),
+        (
+            "/wiki/Ada".to_string(),
+            vec!["/wiki/Pascal".to_string(), "/wiki/C".to_string()],
+        ),
+    ]);
+
+    assert_eq!(
+        to_edgelist(&graph),
+        "/wiki/Ada\t/wiki/C\n/wiki/Ada\t/wiki/Pascal\n/wiki/Zig\t/wiki/C\n"
+    );
+}
+
+#[test]
+fn degree_stats_count_distinct_links_most_linked_first() {
+    let graph = HashMap::from([
+        (
+            "/wiki/Rust".to_string(),
+            vec![
+                "/wiki/C".to_string(),
+                "/wiki/Go".to_string(),
+                "/wiki/C".to_string(),
+            ],
+        ),
+        ("/wiki/Go".to_string(), vec!["/wiki/C".to_string()]),
+    ]);
+    let stats = degree_stats(&graph, false);
+
+    let degrees = stats
+        .iter()
+        .map(|node| (node.node.as_str(), node.in_degree, node.out_degree))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        degrees,
+        [("/wiki/C", 2, 0), ("/wiki/Go", 1, 1), ("/wiki/Rust", 0, 2)]
+    );
+    assert!(stats.iter().all(|node| node.pagerank.is_none()));
+}
+
+#[test]
+fn pagerank_adds_up_to_one_and_favours_linked_pages() {
+    let graph = HashMap::from([
+        ("/wiki/Rust".to_string(), vec!["/wiki/C".to_string()]),
+        ("/wiki/Go".to_string(), vec!["/wiki/C".to_string()]),
+        ("/wiki/C".to_string(), vec!["/wiki/Rust".to_string()]),
+    ]);
+    let ranks = pagerank(&graph);
+
+    assert!((ranks.values().sum::<f64>() - 1.0).abs() < 1e-6);
+    assert!(ranks["/wiki/C"] > ranks["/wiki/Rust"]);
+    assert!(ranks["/wiki/Rust"] > ranks["/wiki/Go"]);
+}
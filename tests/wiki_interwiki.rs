@@ -0,0 +1,42 @@
+use scraprs::wiki::fetch_interwiki_links;
+
+static ARTICLE_HTML: &str = include_str!("fixtures/wiki_article.html");
+
+#[test]
+fn language_links_are_keyed_by_their_language() {
+    let links = fetch_interwiki_links(ARTICLE_HTML);
+
+    assert_eq!(links.len(), 3);
+    assert_eq!(
+        links["de"],
+        "https://de.wikipedia.org/wiki/Rust_(Programmiersprache)"
+    );
+    assert_eq!(links["fr"], "https://fr.wikipedia.org/wiki/Rust_(langage)");
+    // Links without a `hreflang` fall back to the language class of their list item
+    assert!(links["zh-min-nan"].starts_with("https://zh-min-nan.wikipedia.org/wiki/Rust_"));
+}
+
+#[test]
+fn language_dropdown_links_are_collected_as_well() {
+    let html = r#"<html><body>
+        <div id="p-lang-btn" class="vector-dropdown mw-portlet mw-portlet-lang">
+          <div class="vector-dropdown-content">
+            <div class="vector-menu-content"><ul class="vector-menu-content-list">
+              <li class="interlanguage-link interwiki-es mw-list-item"><a href="https://es.wikipedia.org/wiki/Rust_(lenguaje_de_programaci%C3%B3n)" hreflang="es" lang="es">Español</a></li>
+            </ul></div>
+            <a href="https://ja.wikipedia.org/wiki/Rust_(%E3%83%97%E3%83%AD%E3%82%B0%E3%83%A9%E3%83%9F%E3%83%B3%E3%82%B0%E8%A8%80%E8%AA%9E)" hreflang="ja">日本語</a>
+            <a href="https://www.wikidata.org/wiki/Special:EntityPage/Q575650#sitelinks-wikipedia">Edit links</a>
+          </div>
+        </div>
+    </body></html>"#;
+
+    let mut languages = fetch_interwiki_links(html).into_keys().collect::<Vec<_>>();
+    languages.sort();
+
+    assert_eq!(languages, ["es", "ja"]);
+}
+
+#[test]
+fn pages_without_a_language_panel_have_no_links() {
+    assert!(fetch_interwiki_links("<html><body><p>Stub</p></body></html>").is_empty());
+}
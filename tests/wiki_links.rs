@@ -0,0 +1,346 @@
+use scraper::Html;
+use scraprs::wiki::{
+    base_wiki_url, fetch_external_links, fetch_wiki_link_counts, parse_wiki_links,
+    parse_wiki_ref_list, LinkFilter, LinkScope, Namespace, WikiLink, CONTENT_SELECTOR,
+    PARAGRAPH_LINK_SELECTOR,
+};
+use std::collections::HashSet;
+
+static ARTICLE_HTML: &str = include_str!("fixtures/wiki_article.html");
+
+fn filtered_links(dedup: bool, include_namespaces: bool) -> Vec<WikiLink> {
+    let filter = LinkFilter {
+        dedup,
+        include_namespaces,
+        ..LinkFilter::default()
+    };
+    parse_wiki_links(ARTICLE_HTML, filter).expect("fixture to parse")
+}
+
+fn hrefs(links: &[WikiLink]) -> Vec<&str> {
+    links.iter().map(|link| link.href.as_str()).collect()
+}
+
+#[test]
+fn article_links_are_kept_in_order() {
+    let links = filtered_links(true, false);
+
+    assert_eq!(
+        hrefs(&links),
+        [
+            "/wiki/Multi-paradigm_programming_language",
+            "/wiki/General-purpose_programming_language",
+            "/wiki/Computer_performance",
+            "/wiki/Type_safety",
+            "/wiki/Concurrency_(computer_science)",
+            "/wiki/Mozilla",
+            "/wiki/Rust_(programming_language)",
+            "/wiki/Star_Wars:_Episode_I_%E2%80%93_The_Phantom_Menace",
+            "/wiki/Ferris_(mascot)",
+        ]
+    );
+}
+
+#[test]
+fn infobox_links_are_excluded() {
+    let links = filtered_links(false, true);
+
+    assert!(hrefs(&links).iter().all(|href| ![
+        "/wiki/Programming_paradigm",
+        "/wiki/Graydon_Hoare"
+    ]
+    .contains(href)));
+}
+
+#[test]
+fn external_citation_and_edit_links_are_excluded() {
+    let links = filtered_links(false, true);
+
+    assert!(hrefs(&links).iter().all(|href| href.starts_with("/wiki/")));
+}
+
+#[test]
+fn self_links_without_href_are_excluded() {
+    let links = filtered_links(false, true);
+
+    assert!(links
+        .iter()
+        .all(|link| link.display_text != "Rust (programming language)"));
+}
+
+#[test]
+fn namespaced_links_are_only_kept_on_request() {
+    let namespaced = [
+        "/wiki/Category:Programming_languages",
+        "/wiki/Help:Contents",
+    ];
+
+    let links = filtered_links(true, false);
+    assert!(hrefs(&links).iter().all(|href| !namespaced.contains(href)));
+
+    let links = filtered_links(true, true);
+    assert!(namespaced.iter().all(|href| hrefs(&links).contains(href)));
+}
+
+#[test]
+fn duplicate_links_are_kept_without_dedup() {
+    let count = |dedup| {
+        filtered_links(dedup, false)
+            .iter()
+            .filter(|link| link.href == "/wiki/Mozilla")
+            .count()
+    };
+
+    assert_eq!(count(false), 2);
+    assert_eq!(count(true), 1);
+}
+
+#[test]
+fn link_texts_fall_back_to_the_title() {
+    let links = filtered_links(false, false);
+    let find = |href: &str| {
+        links
+            .iter()
+            .find(|link| link.href == href)
+            .expect("fixture to have the link")
+    };
+
+    let paradigm = find("/wiki/Multi-paradigm_programming_language");
+    assert_eq!(paradigm.title, "Multi-paradigm programming language");
+    assert_eq!(paradigm.display_text, "multi-paradigm");
+
+    // Anchors without a title fall back to the article name, and image anchors to the title
+    let ferris = find("/wiki/Ferris_(mascot)");
+    assert_eq!(ferris.title, "Ferris (mascot)");
+    assert_eq!(ferris.display_text, "Ferris (mascot)");
+}
+
+#[test]
+fn fragments_are_only_kept_on_request() {
+    let filter = LinkFilter {
+        keep_fragments: true,
+        ..LinkFilter::default()
+    };
+    let links = parse_wiki_links(ARTICLE_HTML, filter).expect("fixture to parse");
+
+    assert!(hrefs(&links).contains(&"/wiki/Rust_(programming_language)#History"));
+}
+
+#[test]
+fn fragments_and_query_strings_collapse_to_the_article() {
+    let html = r#"<div id="bodyContent"><p>
+        <a href="/wiki/Rust">Rust</a>
+        <a href="/wiki/Rust#History">history</a>
+        <a href="/wiki/Rust?action=history">revisions</a>
+        <a href="/wiki/Rust?oldid=1#Syntax">old syntax</a>
+    </p></div>"#;
+
+    let filter = LinkFilter {
+        dedup: true,
+        ..LinkFilter::default()
+    };
+    let links = parse_wiki_links(html, filter.clone()).expect("html to parse");
+    assert_eq!(hrefs(&links), ["/wiki/Rust"]);
+
+    let filter = LinkFilter {
+        keep_fragments: true,
+        ..filter
+    };
+    let links = parse_wiki_links(html, filter).expect("html to parse");
+    assert_eq!(
+        hrefs(&links),
+        ["/wiki/Rust", "/wiki/Rust#History", "/wiki/Rust#Syntax"]
+    );
+}
+
+#[test]
+fn link_counts_include_duplicates() {
+    let counts = fetch_wiki_link_counts(ARTICLE_HTML).expect("fixture to parse");
+
+    assert_eq!(counts["/wiki/Mozilla"], 2);
+    assert_eq!(counts["/wiki/Type_safety"], 1);
+    assert!(!counts.contains_key("/wiki/Category:Programming_languages"));
+    assert_eq!(counts.values().sum::<usize>(), 10);
+}
+
+#[test]
+fn links_are_capped_in_document_order() {
+    let filter = LinkFilter {
+        dedup: true,
+        max_links: Some(3),
+        ..LinkFilter::default()
+    };
+    let links = parse_wiki_links(ARTICLE_HTML, filter).expect("fixture to parse");
+
+    assert_eq!(
+        hrefs(&links),
+        [
+            "/wiki/Multi-paradigm_programming_language",
+            "/wiki/General-purpose_programming_language",
+            "/wiki/Computer_performance",
+        ]
+    );
+}
+
+#[test]
+fn ref_list_skips_comments_and_reports_invalid_lines() {
+    let content = "# seeds\nRust (programming language)\n\n  /wiki/Go  \nhttps://example.com/Go\n";
+    let (refs, invalid) = parse_wiki_ref_list(content);
+
+    assert_eq!(refs, ["/wiki/Rust_(programming_language)", "/wiki/Go"]);
+    assert_eq!(invalid.len(), 1);
+    assert_eq!(invalid[0].0, 5);
+    assert_eq!(invalid[0].1 .0, "https://example.com/Go");
+}
+
+#[test]
+fn external_links_are_deduped_and_leave_out_wikipedia() {
+    let links = fetch_external_links(ARTICLE_HTML).expect("fixture to parse");
+
+    assert_eq!(
+        links,
+        [
+            "https://www.rust-lang.org/",
+            "https://doc.rust-lang.org/book/",
+            "https://github.com/rust-lang/book",
+        ]
+    );
+}
+
+#[test]
+fn red_links_are_skipped_by_default() {
+    let links = filtered_links(true, false);
+
+    assert!(links
+        .iter()
+        .all(|link| !link.href.contains("Oxidized_compiler")));
+}
+
+#[test]
+fn red_links_are_kept_as_their_article_path() {
+    let filter = LinkFilter {
+        dedup: true,
+        include_redlinks: true,
+        ..LinkFilter::default()
+    };
+    let links = parse_wiki_links(ARTICLE_HTML, filter).expect("fixture to parse");
+    let redlink = links
+        .iter()
+        .find(|link| link.href == "/wiki/Oxidized_compiler")
+        .expect("red link to be kept");
+
+    assert_eq!(redlink.title, "Oxidized compiler");
+    assert_eq!(redlink.display_text, "oxidized compiler");
+}
+
+#[test]
+fn classifies_names_by_their_namespace() {
+    assert_eq!(
+        Namespace::of("Rust_(programming_language)"),
+        Namespace::Main
+    );
+    assert_eq!(Namespace::of("Category:Mozilla"), Namespace::Category);
+    assert_eq!(Namespace::of("Image:Ferris.png"), Namespace::File);
+    assert_eq!(
+        Namespace::of("Star_Wars:_Episode_I_%E2%80%93_The_Phantom_Menace"),
+        Namespace::Main
+    );
+    assert_eq!(
+        Namespace::of("Wikipedia:About"),
+        Namespace::Other("Wikipedia".to_string())
+    );
+}
+
+#[test]
+fn keeps_only_the_allowed_namespaces() {
+    let filter = LinkFilter {
+        dedup: true,
+        namespaces: Some(HashSet::from([Namespace::Category, Namespace::Help])),
+        ..LinkFilter::default()
+    };
+    let links = parse_wiki_links(ARTICLE_HTML, filter).expect("fixture to parse");
+
+    assert!(!links.is_empty());
+    assert!(links
+        .iter()
+        .all(|link| matches!(link.namespace, Namespace::Category | Namespace::Help)));
+    assert!(hrefs(&links).contains(&"/wiki/Category:Programming_languages"));
+}
+
+#[test]
+fn compiled_selectors_match_the_paragraph_links() {
+    let document = Html::parse_document(ARTICLE_HTML);
+    let content = document
+        .select(&CONTENT_SELECTOR)
+        .next()
+        .expect("fixture to have the main content div");
+
+    // Infobox and references links are outside of the paragraphs
+    let anchors = content.select(&PARAGRAPH_LINK_SELECTOR).count();
+    assert_eq!(anchors, 16);
+}
+
+#[test]
+fn wider_link_scopes_collect_lists_and_the_infobox() {
+    let scoped_links = |scope| {
+        let filter = LinkFilter {
+            dedup: true,
+            scope,
+            ..LinkFilter::default()
+        };
+        parse_wiki_links(ARTICLE_HTML, filter).expect("fixture to parse")
+    };
+
+    let paragraphs = scoped_links(LinkScope::Paragraphs);
+    assert!(!hrefs(&paragraphs).contains(&"/wiki/Cargo_(package_manager)"));
+
+    let lists = scoped_links(LinkScope::Lists);
+    assert!(hrefs(&lists).contains(&"/wiki/Cargo_(package_manager)"));
+    assert!(!hrefs(&lists).contains(&"/wiki/Graydon_Hoare"));
+
+    let body = scoped_links(LinkScope::Body);
+    assert!(hrefs(&body).contains(&"/wiki/Cargo_(package_manager)"));
+    assert_eq!(
+        hrefs(&body)[..2],
+        ["/wiki/Programming_paradigm", "/wiki/Graydon_Hoare"]
+    );
+}
+
+#[test]
+fn links_before_the_first_heading_are_in_the_lead() {
+    let filter = LinkFilter {
+        dedup: true,
+        scope: LinkScope::Lists,
+        ..LinkFilter::default()
+    };
+    let links = parse_wiki_links(ARTICLE_HTML, filter).expect("fixture to parse");
+    let in_lead = |href: &str| {
+        links
+            .iter()
+            .find(|link| link.href == href)
+            .expect("fixture to have the link")
+            .in_lead
+    };
+
+    assert!(in_lead("/wiki/Multi-paradigm_programming_language"));
+    assert!(in_lead("/wiki/Ferris_(mascot)"));
+    // See also section comes after the first heading
+    assert!(!in_lead("/wiki/Cargo_(package_manager)"));
+}
+
+#[test]
+fn invalid_language_codes_are_rejected() {
+    assert_eq!(
+        base_wiki_url("fr").expect("to be a valid language code"),
+        "https://fr.wikipedia.org"
+    );
+    assert!(base_wiki_url("").is_err());
+    assert!(base_wiki_url("en.evil.com/").is_err());
+}
+
+#[test]
+fn pages_without_main_content_fail_to_parse() {
+    let html = "<html><body><div id=\"content\"><p><a href=\"/wiki/Rust\">Rust</a></p></div></body></html>";
+
+    assert!(parse_wiki_links(html, LinkFilter::default()).is_err());
+}
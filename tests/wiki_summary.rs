@@ -0,0 +1,48 @@
+use scraper::Html;
+use scraprs::wiki::{parse_page_title, parse_wiki_summary};
+
+static ARTICLE_HTML: &str = include_str!("fixtures/wiki_article.html");
+
+#[test]
+fn summary_is_the_first_paragraph_without_citations() {
+    let summary = parse_wiki_summary(ARTICLE_HTML).expect("fixture to parse");
+
+    assert_eq!(
+        summary,
+        "Rust is a multi-paradigm, general-purpose programming language. \
+         Rust emphasizes performance, type safety, and concurrency."
+    );
+}
+
+#[test]
+fn empty_paragraphs_are_skipped() {
+    let html = r##"<div id="bodyContent">
+        <p class="mw-empty-elt"></p>
+        <p>   </p>
+        <p><b>Ferris</b> is the unofficial mascot of Rust.<sup class="reference"><a href="#cite_note-2">[2]</a></sup></p>
+    </div>"##;
+
+    let summary = parse_wiki_summary(html).expect("html to parse");
+    assert_eq!(summary, "Ferris is the unofficial mascot of Rust.");
+}
+
+#[test]
+fn page_title_is_the_text_of_the_first_heading() {
+    let document = Html::parse_document(ARTICLE_HTML);
+    assert_eq!(
+        parse_page_title(&document).as_deref(),
+        Some("Rust (programming language)")
+    );
+
+    // Formatting markup of the heading is left out of the title
+    let document = Html::parse_document(
+        r#"<h1 id="firstHeading" class="firstHeading"><i>The Hobbit</i>
+        (film series)</h1>"#,
+    );
+    assert_eq!(
+        parse_page_title(&document).as_deref(),
+        Some("The Hobbit (film series)")
+    );
+
+    assert_eq!(parse_page_title(&Html::parse_document("<p>Rust</p>")), None);
+}